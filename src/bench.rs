@@ -0,0 +1,109 @@
+//
+// bench.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use day10::{ Knot, KnotHash, ReferenceKnot };
+use failure::Error;
+use std::time::{ Duration, Instant };
+use Solution;
+
+
+/// Summary of the times measured for repeated runs of the same code.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Stats
+{
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Stats
+{
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort();
+        Stats { min: samples[0], median: samples[samples.len() / 2] }
+    }
+}
+
+
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Timings
+{
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+
+#[inline]
+fn timed<T, F>(samples: &mut Vec<Duration>, f: F) -> T
+    where F: FnOnce() -> T
+{
+    let start = Instant::now();
+    let result = f();
+    samples.push(start.elapsed());
+    result
+}
+
+
+/// Runs the parsing and each part of a solution a number of times,
+/// measuring each step separately.
+pub fn bench<S: Solution>(input: &str, runs: usize) -> Result<Timings, Error>
+{
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+
+    for _ in 0 .. runs {
+        let input = timed(&mut parse, || S::parse(input))?;
+        timed(&mut part1, || S::part1(&input))?;
+        timed(&mut part2, || S::part2(&input))?;
+    }
+
+    Ok(Timings {
+        parse: Stats::from_samples(parse),
+        part1: Stats::from_samples(part1),
+        part2: Stats::from_samples(part2),
+    })
+}
+
+
+/// Benchmarks the solution for a given day.
+pub fn bench_day(day: u32, input: &str, runs: usize) -> Result<Timings, Error>
+{
+    with_solution!(day, bench(input, runs))
+}
+
+
+/// Compares the knot-tying engine used by `KnotHash` with the reference
+/// implementation, by calculating the sparse hashes used to build a day 14
+/// grid a number of times. Returns the timings for the reference and the
+/// fast implementations, in that order.
+pub fn bench_knot(runs: usize) -> Result<(Stats, Stats), Error>
+{
+    let rows: Vec<Vec<usize>> = (0 .. 128)
+        .map(|row| format!("flqrgnkx-{}", row).bytes().map(usize::from).collect())
+        .collect();
+
+    let mut reference = Vec::with_capacity(runs);
+    let mut fast = Vec::with_capacity(runs);
+    for _ in 0 .. runs {
+        let expected: Vec<Vec<u32>> = timed(&mut reference, || rows.iter().map(|lengths| {
+            let mut knot = ReferenceKnot::new(KnotHash::SIZE);
+            knot.rounds(lengths, &KnotHash::SUFFIX, KnotHash::ROUNDS);
+            knot.sparse_hash().to_vec()
+        }).collect());
+        let actual: Vec<Vec<u32>> = timed(&mut fast, || rows.iter().map(|lengths| {
+            let mut knot = Knot::new(KnotHash::SIZE);
+            knot.rounds(lengths, &KnotHash::SUFFIX, KnotHash::ROUNDS);
+            knot.sparse_hash().to_vec()
+        }).collect());
+        ensure!(actual == expected, "Knot hash implementations produced different results");
+    }
+
+    Ok((Stats::from_samples(reference), Stats::from_samples(fast)))
+}
@@ -0,0 +1,246 @@
+//
+// aoc.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+extern crate aoc2017;
+#[macro_use] extern crate failure;
+
+use aoc2017::{ bench, Part };
+use aoc2017::verify::{ self, Status };
+use failure::Error;
+use std::env;
+use std::io::{ self, Read };
+use std::path::Path;
+use std::process;
+use std::time::Duration;
+
+
+const USAGE: &str = "\
+Usage: aoc run <day> [--part a|b] [--input <path>]
+       aoc verify [--dir <path>]
+       aoc bench [<day>...] [--runs <n>] [--dir <path>]
+       aoc bench --knothash [--runs <n>]
+       aoc help
+
+The 'run' command runs the solution for a given day. Both parts are
+solved unless --part is used. The puzzle input is read from standard
+input unless a path is given with --input.
+
+The 'verify' command checks the solutions against the answers listed
+in the 'answers' file of a directory (default: inputs), using the
+dayNN.txt input files from the same directory.
+
+The 'bench' command times the parsing and each part of the solutions
+for the given days (default: all which have an input file in the
+directory) over a number of runs (default: 5), and reports the minimum
+and median times. With --knothash, the knot-tying implementation used by
+the knot hash is compared with the simpler reference one instead.";
+
+
+struct RunOptions
+{
+    day: u32,
+    parts: Vec<Part>,
+    input: Option<String>,
+}
+
+impl RunOptions
+{
+    fn parse<I: Iterator<Item=String>>(mut args: I) -> Result<Self, Error>
+    {
+        let mut day = None;
+        let mut parts = vec![Part::A, Part::B];
+        let mut input = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-p" | "--part" => match args.next() {
+                    Some(value) => parts = vec![value.parse()?],
+                    None => bail!("Option '{}' requires a value", arg),
+                },
+                "-i" | "--input" => match args.next() {
+                    Some(value) => input = Some(value),
+                    None => bail!("Option '{}' requires a value", arg),
+                },
+                _ if day.is_none() => day = Some(arg.parse()?),
+                _ => bail!("Unexpected argument '{}'", arg),
+            }
+        }
+
+        let day = if let Some(day) = day { day } else {
+            bail!("No day specified")
+        };
+        ensure!((1 ..= aoc2017::DAYS).contains(&day),
+                "Day must be in the 1-{} range", aoc2017::DAYS);
+
+        Ok(RunOptions { day, parts, input })
+    }
+}
+
+
+fn read_input(path: &Option<String>) -> Result<String, Error>
+{
+    match *path {
+        Some(ref path) => verify::read_file(Path::new(path)),
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            Ok(input)
+        },
+    }
+}
+
+
+fn run<I: Iterator<Item=String>>(args: I) -> Result<(), Error>
+{
+    let options = RunOptions::parse(args)?;
+    let input = read_input(&options.input)?;
+    for &part in &options.parts {
+        let answer = aoc2017::solve(options.day, part, &input).map_err(|e| {
+            let name = options.input.as_deref().unwrap_or("<stdin>");
+            aoc2017::name_input_error(e, name)
+        })?;
+        println!("{}", answer);
+    }
+    Ok(())
+}
+
+
+fn format_duration(d: Duration) -> String
+{
+    let secs = d.as_secs_f64();
+    if secs < 1e-3 {
+        format!("{:.3}µs", secs * 1e6)
+    } else if secs < 1.0 {
+        format!("{:.3}ms", secs * 1e3)
+    } else {
+        format!("{:.3}s", secs)
+    }
+}
+
+
+fn verify<I: Iterator<Item=String>>(mut args: I) -> Result<(), Error>
+{
+    let mut dir = verify::DEFAULT_DIR.to_string();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-d" | "--dir" => match args.next() {
+                Some(value) => dir = value,
+                None => bail!("Option '{}' requires a value", arg),
+            },
+            _ => bail!("Unexpected argument '{}'", arg),
+        }
+    }
+
+    let outcomes = verify::verify_dir(Path::new(&dir))?;
+    let (mut passed, mut mismatched, mut failed) = (0, 0, 0);
+    for outcome in &outcomes {
+        let (status, detail) = match outcome.status {
+            Status::Pass => {
+                passed += 1;
+                ("PASS", String::new())
+            },
+            Status::Mismatch(ref value) => {
+                mismatched += 1;
+                ("MISMATCH", format!("expected {}, got {}", outcome.answer.value, value))
+            },
+            Status::Fail(ref e) => {
+                failed += 1;
+                ("FAIL", e.to_string())
+            },
+        };
+        let line = format!("day {:02} {}  {:8}  {:>12}  {}", outcome.answer.day,
+                           outcome.answer.part, status,
+                           format_duration(outcome.elapsed), detail);
+        println!("{}", line.trim_end());
+    }
+    println!("{} passed, {} mismatched, {} failed", passed, mismatched, failed);
+
+    ensure!(passed == outcomes.len(), "Verification failed");
+    Ok(())
+}
+
+
+fn bench<I: Iterator<Item=String>>(mut args: I) -> Result<(), Error>
+{
+    let mut dir = verify::DEFAULT_DIR.to_string();
+    let mut runs = 5;
+    let mut days = Vec::new();
+    let mut knothash = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-k" | "--knothash" => knothash = true,
+            "-d" | "--dir" => match args.next() {
+                Some(value) => dir = value,
+                None => bail!("Option '{}' requires a value", arg),
+            },
+            "-r" | "--runs" => match args.next() {
+                Some(value) => runs = value.parse()?,
+                None => bail!("Option '{}' requires a value", arg),
+            },
+            _ => days.push(arg.parse()?),
+        }
+    }
+    ensure!(runs > 0, "The number of runs must be positive");
+
+    if knothash {
+        ensure!(days.is_empty(), "Days cannot be specified with --knothash");
+        let (reference, fast) = bench::bench_knot(runs)?;
+        println!("{:9}  {:>12} {:>12}", "knot", "min", "median");
+        for &(name, stats) in &[("reference", reference), ("fast", fast)] {
+            println!("{:9}  {:>12} {:>12}", name,
+                     format_duration(stats.min), format_duration(stats.median));
+        }
+        println!("speedup: {:.2}x", reference.median.as_secs_f64() / fast.median.as_secs_f64());
+        return Ok(());
+    }
+
+    let dir = Path::new(&dir);
+    if days.is_empty() {
+        days = (1 ..= aoc2017::DAYS)
+            .filter(|&day| verify::input_path(dir, day).is_file())
+            .collect();
+    }
+
+    println!("day  {:>12} {:>12}  {:>12} {:>12}  {:>12} {:>12}",
+             "parse min", "median", "part a min", "median", "part b min", "median");
+    for day in days {
+        let input = verify::read_file(&verify::input_path(dir, day))?;
+        let t = bench::bench_day(day, &input, runs)?;
+        println!("{:02}   {:>12} {:>12}  {:>12} {:>12}  {:>12} {:>12}", day,
+                 format_duration(t.parse.min), format_duration(t.parse.median),
+                 format_duration(t.part1.min), format_duration(t.part1.median),
+                 format_duration(t.part2.min), format_duration(t.part2.median));
+    }
+    Ok(())
+}
+
+
+fn main()
+{
+    let mut args = env::args().skip(1);
+    let result = match args.next().as_deref() {
+        Some("run") => run(args),
+        Some("verify") => verify(args),
+        Some("bench") => bench(args),
+        Some("help") | Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        command => {
+            match command {
+                Some(command) => eprintln!("aoc: Invalid command '{}'", command),
+                None => eprintln!("aoc: No command specified"),
+            }
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("aoc: {}", e);
+        process::exit(1);
+    }
+}
@@ -6,22 +6,10 @@
 
 extern crate aoc2017;
 
-use std::io::{ self, Read };
-use aoc2017::iter_digits;
+use aoc2017::{ day01::Day01, Part };
 
 
 fn main()
 {
-    let digits: Vec<_> = iter_digits(io::stdin().bytes()).collect();
-
-    let mut sum = 0;
-    let step = digits.len() / 2;
-
-    for (index, value) in digits.iter().enumerate() {
-        if *value == digits[(index + step) % digits.len()] {
-            sum += *value;
-        }
-    }
-
-    println!("{}", sum);
+    aoc2017::main::<Day01>(&[Part::B])
 }
@@ -6,21 +6,10 @@
 
 extern crate aoc2017;
 
-use aoc2017::{ rows_of_digits, Permutations };
-use std::io;
+use aoc2017::{ day02::Day02, Part };
 
 
 fn main()
 {
-    let stdin = io::stdin();
-    let mut sum = 0;
-    for row in rows_of_digits(stdin.lock()) {
-        for (a, b) in row.permutations() {
-            if a != b && a % b == 0 {
-                sum += a / b;
-                break;
-            }
-        }
-    }
-    println!("{}", sum);
+    aoc2017::main::<Day02>(&[Part::B])
 }
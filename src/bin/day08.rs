@@ -4,196 +4,159 @@
 // Distributed under terms of the MIT license.
 //
 
+extern crate aoc2017;
 #[macro_use] extern crate failure;
+extern crate num;
 
-use std::collections::HashMap;
-use std::fmt;
-use std::io;
-use std::io::prelude::*;
-use std::str::FromStr;
+use aoc2017::day08::{ analyze, eliminate_dead, Checked, Debugger, Machine, Program, RegisterValue };
+use aoc2017::{ name_input_error, verify };
+use failure::Error;
+use num::BigInt;
+use std::env;
+use std::io::{ self, Read };
+use std::path::Path;
+use std::process;
 
 
-#[derive(Debug)]
-enum Cond
-{
-    EQ(String, i32),  // <name> == <num>
-    NE(String, i32),  // <name> != <num>
-    GT(String, i32),  // <name> >  <num>
-    GE(String, i32),  // <name> >= <num>
-    LT(String, i32),  // <name> <  <num>
-    LE(String, i32),  // <name> <= <num>
-}
+const USAGE: &str = "\
+Usage: day08 [--registers <type>] [--trace] [<program>]
+       day08 [--registers <type>] --debug <program>
+       day08 (--analyze | --eliminate) [<program>]
 
-impl FromStr for Cond
-{
-    type Err = ::failure::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err>
-    {
-        if let Some(pos) = s.find(" == ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Cond::EQ(regname.trim().to_string(), value[4..].trim().parse()?))
-        } else if let Some(pos) = s.find(" != ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Cond::NE(regname.trim().to_string(), value[4..].trim().parse()?))
-        } else if let Some(pos) = s.find(" >= ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Cond::GE(regname.trim().to_string(), value[4..].trim().parse()?))
-        } else if let Some(pos) = s.find(" <= ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Cond::LE(regname.trim().to_string(), value[4..].trim().parse()?))
-        } else if let Some(pos) = s.find(" > ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Cond::GT(regname.trim().to_string(), value[3..].trim().parse()?))
-        } else if let Some(pos) = s.find(" < ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Cond::LT(regname.trim().to_string(), value[3..].trim().parse()?))
-        } else {
-            bail!("Condition '{}' has an invalid relational operator", s)
-        }
-    }
-}
+Runs a register program, read from a file or the standard input, and
+prints the largest final value of a register, and the largest value held
+by any register while running.
 
+With --trace, a line is printed for each executed instruction, including
+the value of its condition and the change made to a register. With
+--debug, an interactive debugger reads commands from the standard input.
+
+The --registers option selects the type of the register values: checked
+(the default) uses 32-bit values and reports the line at which an overflow
+happens, i32 and i64 wrap around on overflow, and bigint never overflows.
+
+With --analyze, the program is not run: instead, a report lists registers
+which are only read, only written, or never affect a condition, and the
+instructions which can never be executed. With --eliminate, the program
+is printed without those instructions.";
 
-#[derive(Debug)]
-enum Op
-{
-    Inc(String, i32),  // <name> inc <num>
-    Dec(String, i32),  // <name> dec <num>
-}
 
-impl FromStr for Op
+fn input_name(path: &Option<String>) -> &str
 {
-    type Err = ::failure::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err>
-    {
-        if let Some(pos) = s.find(" inc ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Op::Inc(regname.trim().to_string(), value[5..].trim().parse()?))
-        } else if let Some(pos) = s.find(" dec ") {
-            let (regname, value) = s.split_at(pos);
-            Ok(Op::Dec(regname.trim().to_string(), value[5..].trim().parse()?))
-        } else {
-            bail!("Instruction '{}' contains invalid operation", s)
-        }
-    }
+    path.as_deref().unwrap_or("<stdin>")
 }
 
 
-#[derive(Debug)]
-struct Instr
+fn load(path: &Option<String>) -> Result<Program, Error>
 {
-    op: Op,
-    cond: Cond,
+    let input = match *path {
+        Some(ref path) => verify::read_file(Path::new(path))?,
+        None => {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            input
+        },
+    };
+    input.parse().map_err(|e| name_input_error(e, input_name(path)))
 }
 
-impl FromStr for Instr
+
+fn run<V: RegisterValue>(path: &Option<String>, trace: bool) -> Result<(), Error>
 {
-    type Err = ::failure::Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err>
-    {
-        let if_pos = if let Some(pos) = s.find(" if ") { pos } else {
-            bail!("Input '{}' does not contain 'if'", s);
-        };
-        let (op_str, cond_str) = s.split_at(if_pos);
-        Ok(Instr {
-            op: op_str.trim().parse()?,
-            cond: cond_str[4..].trim().parse()?,
-        })
+    let program = load(path)?;
+    let mut machine = Machine::<V>::new();
+    let result = if trace {
+        let stdout = io::stdout();
+        machine.trace(&program, stdout.lock())
+    } else {
+        machine.run(&program)
+    };
+    result.map_err(|e| name_input_error(e, input_name(path)))?;
+    match (machine.max_value(), machine.peak()) {
+        (Some(value), Some(peak)) => {
+            println!("{}", value);
+            println!("{}", peak.value);
+            if trace {
+                println!("peak: register {}, line {}", peak.register, peak.line);
+            }
+            Ok(())
+        },
+        _ => bail!("No register was written to"),
     }
 }
 
 
-struct Machine
+fn debug<V: RegisterValue>(path: &Option<String>) -> Result<(), Error>
 {
-    regs: HashMap<String, i32>,
-    max_seen: i32,
+    let program = load(path)?;
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    Debugger::<V>::new(&program).interact(stdin.lock(), stdout.lock())
 }
 
 
-impl Machine
+fn report(path: &Option<String>, eliminate: bool) -> Result<(), Error>
 {
-    fn new() -> Self {
-        Machine {
-            regs: HashMap::new(),
-            max_seen: i32::min_value(),
-        }
-    }
-
-    #[inline]
-    fn get(&self, regname: &str) -> i32 {
-        *self.regs.get(regname).unwrap_or(&0)
-    }
-
-    fn check_condition(&self, cond: &Cond) -> bool {
-        match *cond {
-            Cond::EQ(ref r, v) => self.get(r) == v,
-            Cond::NE(ref r, v) => self.get(r) != v,
-            Cond::GT(ref r, v) => self.get(r) >  v,
-            Cond::GE(ref r, v) => self.get(r) >= v,
-            Cond::LT(ref r, v) => self.get(r) <  v,
-            Cond::LE(ref r, v) => self.get(r) <= v,
-        }
-    }
-
-    fn execute(&mut self, ins: &Instr) {
-        if self.check_condition(&ins.cond) {
-            match ins.op {
-                Op::Inc(ref r, v) => self.inc(r, v),
-                Op::Dec(ref r, v) => self.dec(r, v),
-            }
-        }
+    let program = load(path)?;
+    if eliminate {
+        print!("{}", eliminate_dead(&program));
+    } else {
+        print!("{}", analyze(&program));
     }
+    Ok(())
+}
 
-    #[inline]
-    fn inc(&mut self, regname: &str, value: i32) {
-        let reg = self.regs.entry(regname.to_string()).or_insert(0);
-        *reg += value;
-        if *reg > self.max_seen {
-            self.max_seen = *reg;
-        }
-    }
 
-    #[inline]
-    fn dec(&mut self, regname: &str, value: i32) {
-        let reg =  self.regs.entry(regname.to_string()).or_insert(0);
-        *reg -= value;
-        if *reg > self.max_seen {
-            self.max_seen = *reg;
-        }
+fn start<V: RegisterValue>(mode: &str, path: &Option<String>) -> Result<(), Error>
+{
+    match mode {
+        "debug" => debug::<V>(path),
+        _ => run::<V>(path, mode == "trace"),
     }
 }
 
-impl fmt::Display for Machine
+
+fn usage() -> !
 {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
-    {
-        for (regname, value) in &self.regs {
-            write!(f, "{}: {}\n", regname, value)?;
-        }
-        Ok(())
-    }
+    eprintln!("{}", USAGE);
+    process::exit(2);
 }
 
 
 fn main()
 {
-    let mut m = Machine::new();
-
-    let stdin = io::stdin();
-    for line in stdin.lock().lines().filter_map(io::Result::ok) {
-        let instr = line.parse().unwrap();
-        m.execute(&instr);
+    let (mut mode, mut path, mut registers) = ("", None, String::from("checked"));
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--trace" if mode.is_empty() => mode = "trace",
+            "--debug" if mode.is_empty() => mode = "debug",
+            "--analyze" if mode.is_empty() => mode = "analyze",
+            "--eliminate" if mode.is_empty() => mode = "eliminate",
+            "--registers" => registers = args.next().unwrap_or_else(|| usage()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return;
+            },
+            _ if path.is_none() && !arg.starts_with('-') => path = Some(arg),
+            _ => usage(),
+        }
+    }
+    if mode == "debug" && path.is_none() {
+        usage();
     }
 
-    let mut max_value = i32::min_value();
-    for (_regname, &value) in &m.regs {
-        if value > max_value {
-            max_value = value;
-        }
+    let result = match (mode, registers.as_str()) {
+        ("analyze", _) | ("eliminate", _) => report(&path, mode == "eliminate"),
+        (_, "i32") => start::<i32>(mode, &path),
+        (_, "i64") => start::<i64>(mode, &path),
+        (_, "checked") => start::<Checked>(mode, &path),
+        (_, "bigint") => start::<BigInt>(mode, &path),
+        _ => Err(format_err!("Invalid register type '{}'", registers)),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(1);
     }
-    println!("max reg value: {}", max_value);
-    println!("max seen value: {}", m.max_seen);
 }
@@ -4,44 +4,12 @@
 // Distributed under terms of the MIT license.
 //
 
-use std::io::{ self, Read };
+extern crate aoc2017;
+
+use aoc2017::{ day09::Day09, Part };
 
 
 fn main()
 {
-    let mut in_comment = false;
-    let mut skip_next = false;
-    let mut ngroups = 0;
-    let mut nchars = 0;
-    let mut score = 0;
-    let mut score_stack = Vec::new();
-    score_stack.push(0);
-
-    let stdin = io::stdin();
-    for ch in stdin.lock().bytes().filter_map(Result::ok) {
-        if skip_next {
-            skip_next = false;
-            continue;
-        }
-        match ch {
-            b'{' if !in_comment => {
-                ngroups += 1;
-                // Score for a group is "one more than the score of the
-                // group that contains it".
-                let current_score = score_stack.last().unwrap() + 1;
-                score_stack.push(current_score);
-            },
-            b'}' if !in_comment => {
-                score += score_stack.pop().unwrap();
-            },
-            b'!' => skip_next = true,
-            b'<' if !in_comment => in_comment = true,
-            b'>' if in_comment => in_comment = false,
-            _ if in_comment => nchars += 1,
-            _ => (),  // Do nothing.
-        };
-    }
-    println!("Groups: {}", ngroups);
-    println!(" Score: {}", score);
-    println!("NChars: {}", nchars);
+    aoc2017::main::<Day09>(&[Part::A, Part::B])
 }
@@ -6,36 +6,38 @@
 
 extern crate aoc2017;
 
-use aoc2017::day14::{ make_row_hash, hex_char_to_u8 };
-use std::io;
-use std::io::prelude::*;
+use aoc2017::day14::DiskOptions;
+use std::env;
+use std::process;
 
 
-fn hex_string_bits(s: &str) -> u32 {
-    let mut bits = 0;
-    for c in s.chars() {
-        let mut num = hex_char_to_u8(c);
-        while num != 0 {
-            if num & 0x1 == 0x1 {
-                bits += 1;
-            }
-            num >>= 1;
-        }
-    }
-    bits
+fn usage() -> !
+{
+    eprintln!("Usage: day14 {}", DiskOptions::USAGE);
+    process::exit(2);
 }
 
 
 fn main()
 {
-    let stdin = io::stdin();
-    for line in stdin.lock().lines().filter_map(Result::ok) {
-        let input_key = line.trim();
-        let mut used_bits = 0;
-        for row in 0 .. 128 {
-            let row_hash = make_row_hash(input_key, row);
-            used_bits += hex_string_bits(&row_hash);
+    let mut options = DiskOptions::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match options.parse_arg(&arg, &mut args) {
+            Ok(true) => (),
+            Ok(false) => usage(),
+            Err(e) => {
+                eprintln!("error: {}", e);
+                usage();
+            },
         }
-        println!("Used cells: {}", used_bits);
+    }
+
+    match options.read_bitmap() {
+        Ok(bitmap) => println!("{}", bitmap.count_used()),
+        Err(e) => {
+            eprintln!("error: {}", e);
+            process::exit(1);
+        },
     }
 }
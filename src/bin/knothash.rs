@@ -0,0 +1,210 @@
+//
+// knothash.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+extern crate aoc2017;
+#[macro_use] extern crate failure;
+
+use aoc2017::day10::{ encode_hex, KnotHash };
+use aoc2017::verify::read_file;
+use failure::Error;
+use std::env;
+use std::fs::File;
+use std::io::{ self, BufRead, Read };
+use std::path::Path;
+use std::process;
+
+
+const USAGE: &str = "\
+Usage: knothash [<file>...]
+       knothash --string <text>...
+       knothash --lines
+       knothash --check [<file>...]
+       knothash --help
+
+Prints the knot hash of each file, or of the standard input if no files
+are given or the file name is '-', as '<hash>  <name>' lines. Contents
+are hashed as-is, including any trailing newline.
+
+With --string, each argument is hashed as text. With --lines, each line
+of the standard input is hashed separately, without its line terminator.
+The output of these modes shows the hashed text in place of a file name,
+and therefore cannot be verified using --check.
+
+With --check, reads lines in the format printed by this program from the
+given files (or the standard input), and checks whether the hashes of
+the named files match.";
+
+
+fn hash_reader<R: Read>(mut reader: R) -> Result<String, Error>
+{
+    let mut kh = KnotHash::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        match reader.read(&mut buffer)? {
+            0 => break,
+            n => kh.update(&buffer[.. n]),
+        }
+    }
+    Ok(encode_hex(&kh.finalize()))
+}
+
+
+fn hash_file(name: &str) -> Result<String, Error>
+{
+    if name == "-" {
+        hash_reader(io::stdin())
+    } else {
+        let file = File::open(name)
+            .map_err(|e| format_err!("Cannot read '{}': {}", name, e))?;
+        hash_reader(file)
+    }
+}
+
+
+fn hash_files(names: &[String]) -> Result<(), Error>
+{
+    if names.is_empty() {
+        println!("{}  -", hash_file("-")?);
+    }
+    let mut failed = 0;
+    for name in names {
+        match hash_file(name) {
+            Ok(hash) => println!("{}  {}", hash, name),
+            Err(e) => {
+                eprintln!("knothash: {}", e);
+                failed += 1;
+            },
+        }
+    }
+    ensure!(failed == 0, "{} file(s) could not be read", failed);
+    Ok(())
+}
+
+
+fn hash_lines() -> Result<(), Error>
+{
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        println!("{}  {}", encode_hex(&KnotHash::digest(line.as_bytes())), line);
+    }
+    Ok(())
+}
+
+
+/// Splits a line in the format `<hash>  <name>` into its two fields. The
+/// name may also be prefixed by an asterisk instead of a second space, as
+/// written by `sha256sum` in binary mode.
+fn parse_check_line(line: &str) -> Option<(&str, &str)>
+{
+    let space = line.find(' ')?;
+    let (hash, rest) = line.split_at(space);
+    let name = if rest.starts_with("  ") || rest.starts_with(" *") {
+        &rest[2 ..]
+    } else {
+        return None;
+    };
+    if hash.len() == 32 && hash.bytes().all(|b| b.is_ascii_hexdigit()) && !name.is_empty() {
+        Some((hash, name))
+    } else {
+        None
+    }
+}
+
+
+fn check(names: &[String]) -> Result<(), Error>
+{
+    let mut listings = Vec::new();
+    if names.is_empty() {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents)?;
+        listings.push(("-".to_string(), contents));
+    }
+    let (mut mismatched, mut unreadable, mut malformed) = (0, 0, 0);
+    for name in names {
+        match read_file(Path::new(name)) {
+            Ok(contents) => listings.push((name.clone(), contents)),
+            Err(e) => {
+                eprintln!("knothash: {}", e);
+                unreadable += 1;
+            },
+        }
+    }
+
+    for (listing, contents) in &listings {
+        for (lineno, line) in contents.lines().enumerate() {
+            let (hash, name) = match parse_check_line(line) {
+                Some(fields) => fields,
+                None => {
+                    if !line.trim().is_empty() {
+                        eprintln!("knothash: {}:{}: improperly formatted line", listing, lineno + 1);
+                        malformed += 1;
+                    }
+                    continue;
+                },
+            };
+            match hash_file(name) {
+                Ok(ref actual) if actual.eq_ignore_ascii_case(hash) => println!("{}: OK", name),
+                Ok(_) => {
+                    println!("{}: FAILED", name);
+                    mismatched += 1;
+                },
+                Err(e) => {
+                    eprintln!("knothash: {}", e);
+                    println!("{}: FAILED open or read", name);
+                    unreadable += 1;
+                },
+            }
+        }
+    }
+
+    if malformed > 0 {
+        eprintln!("knothash: WARNING: {} line(s) improperly formatted", malformed);
+    }
+    if unreadable > 0 {
+        eprintln!("knothash: WARNING: {} file(s) could not be read", unreadable);
+    }
+    if mismatched > 0 {
+        eprintln!("knothash: WARNING: {} computed hash(es) did NOT match", mismatched);
+    }
+    ensure!(mismatched + unreadable + malformed == 0, "Verification failed");
+    Ok(())
+}
+
+
+fn main()
+{
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let mode = match args.first().map(String::as_str) {
+        Some(arg) if arg.starts_with('-') && arg != "-" => args.remove(0),
+        _ => String::new(),
+    };
+
+    let result = match mode.as_str() {
+        "" => hash_files(&args),
+        "-s" | "--string" => {
+            for text in &args {
+                println!("{}  {}", encode_hex(&KnotHash::digest(text.as_bytes())), text);
+            }
+            Ok(())
+        },
+        "-l" | "--lines" if args.is_empty() => hash_lines(),
+        "-c" | "--check" => check(&args),
+        "-h" | "--help" => {
+            println!("{}", USAGE);
+            Ok(())
+        },
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        },
+    };
+
+    if let Err(e) = result {
+        eprintln!("knothash: {}", e);
+        process::exit(1);
+    }
+}
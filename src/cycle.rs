@@ -0,0 +1,127 @@
+//
+// cycle.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+//! Cycle detection for sequences of states `x0, f(x0), f(f(x0)), ...`
+//! produced by repeatedly applying a step function. Only a constant amount
+//! of states is kept in memory. Both algorithms loop forever if the
+//! sequence never repeats, so they must only be used for state machines
+//! with a finite number of states.
+
+
+/// Cycle found in a sequence of states.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Cycle
+{
+    /// Index of the first state which is part of the cycle.
+    pub start: usize,
+    /// Number of states in the cycle.
+    pub length: usize,
+}
+
+impl Cycle
+{
+    /// Number of steps after which a state is seen for the second time.
+    #[inline]
+    pub fn first_repeat(&self) -> usize {
+        self.start + self.length
+    }
+}
+
+
+/// Floyd's "tortoise and hare" cycle detection.
+pub fn floyd<T, F>(initial: T, mut step: F) -> Cycle
+    where T: Clone + Eq, F: FnMut(&T) -> T
+{
+    // Find a repetition x(i) = x(2i), the hare moves twice as fast.
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    // The distance between both is now a multiple of the cycle length:
+    // moving both at the same speed, with the tortoise starting again
+    // from the beginning, they meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    // Walk the cycle once to find its length.
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+
+/// Brent's cycle detection, which usually needs less evaluations of the
+/// step function than Floyd's algorithm.
+pub fn brent<T, F>(initial: T, mut step: F) -> Cycle
+    where T: Clone + Eq, F: FnMut(&T) -> T
+{
+    // Search successive powers of two, which directly yields the length.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Place the hare one cycle length ahead, then move both at the same
+    // speed until they meet at the start of the cycle.
+    let mut start = 0;
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0 .. length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Sequence 0, 1, ..., start + length - 1, which then jumps back to start.
+    fn rho(start: usize, length: usize) -> impl Fn(&usize) -> usize {
+        move |&x| if x + 1 == start + length { start } else { x + 1 }
+    }
+
+    #[test]
+    fn rho_shaped() {
+        for &(start, length) in &[(0, 1), (0, 7), (1, 1), (3, 5), (10, 1), (17, 64)] {
+            let expected = Cycle { start, length };
+            assert_eq!(floyd(0, rho(start, length)), expected);
+            assert_eq!(brent(0, rho(start, length)), expected);
+            assert_eq!(expected.first_repeat(), start + length);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//
+// day01.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use { iter_digits, Solution };
+use std::io::Read;
+
+
+pub struct Day01;
+
+impl Solution for Day01
+{
+    type Input = Vec<u32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let digits = iter_digits(input.as_bytes().bytes()).collect::<Result<Vec<_>, _>>()?;
+        if digits.is_empty() {
+            bail!("Input does not contain any digits");
+        }
+        Ok(digits)
+    }
+
+    fn part1(digits: &Self::Input) -> Result<u32, Error>
+    {
+        let first = digits[0];
+        let mut last = first;
+        let mut sum = 0;
+
+        for &digit in &digits[1..] {
+            if digit == last {
+                sum += last;
+            }
+            last = digit;
+        }
+
+        // Wrap around.
+        if first == last {
+            sum += last;
+        }
+
+        Ok(sum)
+    }
+
+    fn part2(digits: &Self::Input) -> Result<u32, Error>
+    {
+        let mut sum = 0;
+        let step = digits.len() / 2;
+
+        for (index, value) in digits.iter().enumerate() {
+            if *value == digits[(index + step) % digits.len()] {
+                sum += *value;
+            }
+        }
+
+        Ok(sum)
+    }
+}
@@ -0,0 +1,60 @@
+//
+// day02.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use { rows_of_digits, Permutations, Solution };
+
+
+pub struct Day02;
+
+impl Solution for Day02
+{
+    type Input = Vec<Vec<i32>>;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        rows_of_digits(input.as_bytes()).collect()
+    }
+
+    fn part1(rows: &Self::Input) -> Result<i32, Error>
+    {
+        let mut checksum = 0;
+
+        for row in rows {
+            let mut rowmin = i32::MAX;
+            let mut rowmax = i32::MIN;
+            for &value in row {
+                if value < rowmin {
+                    rowmin = value;
+                }
+                if value > rowmax {
+                    rowmax = value;
+                }
+            }
+            if rowmax >= rowmin {
+                checksum += rowmax - rowmin;
+            }
+        }
+
+        Ok(checksum)
+    }
+
+    fn part2(rows: &Self::Input) -> Result<i32, Error>
+    {
+        let mut sum = 0;
+        for row in rows {
+            for (a, b) in row.clone().permutations() {
+                if a != b && b != 0 && a % b == 0 {
+                    sum += a / b;
+                    break;
+                }
+            }
+        }
+        Ok(sum)
+    }
+}
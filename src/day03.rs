@@ -0,0 +1,179 @@
+//
+// day03.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use grid::SparseGrid;
+use Solution;
+
+
+pub fn grid_size_for_cell(cellindex: u32) -> u32
+{
+    let mut size = 1;
+    while cellindex > size * size {
+        size += 2;
+    }
+    size
+}
+
+
+// For a memory grid, the center point is always "1", and the bottom-right
+// corner is "size * size = maxindex":
+//
+//   17  16  15  14  13
+//   18   5   4   3  12
+//   19   6  [1]  2  11
+//   20   7   8  [9] 10
+//   21  22  23  24 [25]  = size * size
+//
+// The position we are searching for is always in the outer ring. We can
+// calculate the position by walking "backwards" in the spiral: The number
+// of steps is (size * size - cellindex).
+//
+fn manhattan_distance_for_cell(cellindex: u32) -> i64
+{
+    let size = grid_size_for_cell(cellindex);
+
+    // XXX: Probably this can be further simplified.
+    let (x, y) = {
+        let steps = size * size - cellindex;
+        if steps < size {
+            // Bottom row.
+            (size - steps - 1, 0)
+        } else {
+            let steps = steps - (size - 1);
+            if steps < size {
+                // Left column.
+                (0, steps)
+            } else {
+                let steps = steps - (size - 1);
+                if steps < size {
+                    // Top column.
+                    (steps, size - 1)
+                } else {
+                    let steps = steps - (size - 1);
+                    assert!(steps < size);
+                    // Right column.
+                    (size - 1, size - steps - 1)
+                }
+            }
+        }
+    };
+
+    let center = (size / 2) as i64;
+
+    (center - x as i64).abs() + (center - y as i64).abs()
+}
+
+
+/// Calculates the sum of the values in the cells adjacent to the given one.
+fn covered_sum(grid: &SparseGrid<u32>, x: isize, y: isize) -> u32
+{
+    let center = *grid.get(x, y);
+    grid.neighbours8(x, y).fold(center, |sum, (nx, ny)| sum + grid.get(nx, ny))
+}
+
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Direction {
+    East,
+    North,
+    West,
+    South,
+}
+
+impl Direction {
+    fn left(&self) -> Self {
+        match *self {
+            Direction::East => Direction::North,
+            Direction::North => Direction::West,
+            Direction::West => Direction::South,
+            Direction::South => Direction::East,
+        }
+    }
+
+    fn ahead(&self, x: isize, y: isize) -> (isize, isize) {
+        match *self {
+            Direction::East  => (x + 1, y),
+            Direction::North => (x, y + 1),
+            Direction::West  => (x - 1, y),
+            Direction::South => (x, y - 1),
+        }
+    }
+}
+
+
+fn calculate(value: u32) -> u32
+{
+    // The spiral starts at the origin with an initial value of one, and
+    // the grid grows as needed while walking it.
+    let mut grid = SparseGrid::new(0);
+    let (mut x, mut y) = (0, 0);
+    grid.set(x, y, 1);
+
+    let mut d = Direction::East;
+    while *grid.get(x, y) <= value {
+        // Turn left if the cell there has not been filled yet,
+        // otherwise just go ahead.
+        let left = d.left();
+        let (lx, ly) = left.ahead(x, y);
+        if !grid.contains(lx, ly) {
+            d = left;
+            x = lx;
+            y = ly;
+        } else {
+            let (ax, ay) = d.ahead(x, y);
+            x = ax;
+            y = ay;
+        }
+        let sum = covered_sum(&grid, x, y);
+        grid.set(x, y, sum);
+    }
+
+    *grid.get(x, y)
+}
+
+
+pub struct Day03;
+
+impl Solution for Day03
+{
+    type Input = u32;
+    type Part1 = i64;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let value: u32 = input.trim().parse()?;
+        ensure!(value > 0, "Cell index must be positive, got {}", value);
+        Ok(value)
+    }
+
+    fn part1(&cellindex: &Self::Input) -> Result<i64, Error>
+    {
+        Ok(manhattan_distance_for_cell(cellindex))
+    }
+
+    fn part2(&value: &Self::Input) -> Result<u32, Error>
+    {
+        Ok(calculate(value))
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn part2_example() {
+        // First values written: 1, 1, 2, 4, 5, 10, 11, 23, 25, 26, 54, ...
+        for &(value, expected) in &[(1, 2), (2, 4), (5, 10), (10, 11), (23, 25),
+                                    (59, 122), (362, 747), (700, 747), (747, 806)] {
+            assert_eq!(Day03::part2(&value).unwrap(), expected, "value {}", value);
+        }
+    }
+}
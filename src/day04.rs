@@ -0,0 +1,66 @@
+//
+// day04.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use permutohedron::Heap;
+use std::collections::HashSet;
+use Solution;
+
+
+fn has_unique_words(line: &str) -> bool
+{
+    let mut seen_words = HashSet::new();
+    for word in line.split_whitespace() {
+        if seen_words.contains(word) {
+            return false;
+        }
+        seen_words.insert(word);
+    }
+    true
+}
+
+
+fn has_no_anagrams(line: &str) -> bool
+{
+    let mut seen_words = HashSet::new();
+    for word in line.split_whitespace() {
+        let mut word_chars: Vec<char> = word.chars().collect();
+        let heap = Heap::new(&mut word_chars);
+        for permutated_chars in heap {
+            let permutation: String = permutated_chars.iter().collect();
+            if seen_words.contains(permutation.as_str()) {
+                return false;
+            }
+        }
+        seen_words.insert(word);
+    }
+    true
+}
+
+
+pub struct Day04;
+
+impl Solution for Day04
+{
+    type Input = Vec<String>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(input.lines().map(str::to_string).collect())
+    }
+
+    fn part1(lines: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(lines.iter().filter(|line| has_unique_words(line)).count())
+    }
+
+    fn part2(lines: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(lines.iter().filter(|line| has_no_anagrams(line)).count())
+    }
+}
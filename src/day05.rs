@@ -0,0 +1,54 @@
+//
+// day05.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use Solution;
+
+
+fn count_steps<F>(mut jumplist: Vec<i32>, update: F) -> u32
+    where F: Fn(i32) -> i32
+{
+    let mut pc: i32 = 0;
+    let mut steps = 0;
+
+    while pc >= 0 && (pc as usize) < jumplist.len() {
+        steps += 1;
+        let jump = jumplist[pc as usize];
+        jumplist[pc as usize] += update(jump);  // Update jump.
+        pc += jump;                             // Apply jump.
+    }
+
+    steps
+}
+
+
+pub struct Day05;
+
+impl Solution for Day05
+{
+    type Input = Vec<i32>;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let mut jumplist = Vec::new();
+        for line in input.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            jumplist.push(line.parse()?);
+        }
+        Ok(jumplist)
+    }
+
+    fn part1(jumplist: &Self::Input) -> Result<u32, Error>
+    {
+        Ok(count_steps(jumplist.clone(), |_| 1))
+    }
+
+    fn part2(jumplist: &Self::Input) -> Result<u32, Error>
+    {
+        Ok(count_steps(jumplist.clone(), |jump| if jump >= 3 { -1 } else { 1 }))
+    }
+}
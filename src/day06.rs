@@ -0,0 +1,111 @@
+//
+// day06.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use cycle::{ self, Cycle };
+use failure::Error;
+use std::fmt;
+use Solution;
+
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Memory
+{
+    banks: Vec<u32>,
+}
+
+impl Memory
+{
+    fn new(banks: Vec<u32>) -> Self {
+        Self { banks }
+    }
+
+    fn find_most_blocks(&self) -> (usize, u32) {
+        let mut max_blocks = 0;
+        let mut max_blocks_index = 0;
+        for (index, blocks) in self.banks.iter().enumerate() {
+            if *blocks > max_blocks {
+                max_blocks_index = index;
+                max_blocks = *blocks;
+            }
+        }
+        (max_blocks_index, max_blocks)
+    }
+
+    fn reallocate(&mut self) {
+        let (mut index, mut nblocks) = self.find_most_blocks();
+
+        // 1. Empty the bank
+        self.banks[index] = 0;
+
+        // 2. Redistribute among banks, starting with the next
+        while nblocks > 0 {
+            index = (index + 1) % self.banks.len();  // Move to next bank index
+            self.banks[index] += 1;                  // Add one block to that bank
+            nblocks -= 1;                            // One less block pending!
+        }
+    }
+}
+
+impl fmt::Display for Memory
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mem<{}>{:?}", self.banks.len(), self.banks)
+    }
+}
+
+
+fn find_cycle(mem: &Memory) -> Cycle
+{
+    cycle::brent(mem.clone(), |m| {
+        let mut next = m.clone();
+        next.reallocate();
+        next
+    })
+}
+
+
+pub struct Day06;
+
+impl Solution for Day06
+{
+    type Input = Memory;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let mut banks = Vec::new();
+        for value in input.split_whitespace() {
+            banks.push(value.parse()?);
+        }
+        ensure!(!banks.is_empty(), "Input does not contain any memory banks");
+        Ok(Memory::new(banks))
+    }
+
+    fn part1(mem: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(find_cycle(mem).first_repeat())
+    }
+
+    fn part2(mem: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(find_cycle(mem).length)
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn example() {
+        let mem = Day06::parse("0 2 7 0").unwrap();
+        assert_eq!(Day06::part1(&mem).unwrap(), 5);
+        assert_eq!(Day06::part2(&mem).unwrap(), 4);
+    }
+}
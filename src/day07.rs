@@ -0,0 +1,183 @@
+//
+// day07.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use ego_tree::{ NodeMut, NodeRef, Tree };
+use ego_tree::iter::Edge;
+use failure::Error;
+use std::collections::HashMap;
+use std::str::FromStr;
+use { parse_lines, Solution };
+
+
+#[derive(Debug)]
+pub struct Tower
+{
+    name: String,
+    weight: u32,
+}
+
+
+impl FromStr for Tower
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let pos = if let Some(p) = s.find(' ') { p } else {
+            bail!("Input '{}' does not contain a space", s)
+        };
+
+        let (name, weight_str) = s.split_at(pos);
+        let weight = weight_str.trim()
+            .trim_start_matches('(')
+            .trim_end_matches(')')
+            .parse()?;
+
+        Ok(Tower { name: name.to_string(), weight })
+    }
+}
+
+
+/// Each line of input is:  <name> (<weight>) [-> child1[, child2, ...]]
+#[derive(Debug)]
+struct TowerLine
+{
+    tower: Tower,
+    children: Vec<String>,
+}
+
+
+impl FromStr for TowerLine
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let (tower_str, child_str) = if let Some(arrow_pos) = s.find("->") {
+            let (left, right) = s.split_at(arrow_pos);
+            (left.trim(), right.trim_start_matches("->").trim())
+        } else {
+            (s, "")
+        };
+
+        let children = if child_str.is_empty() {
+            Vec::new()
+        } else {
+            child_str.split(',').map(|child| child.trim().to_string()).collect()
+        };
+
+        Ok(TowerLine { tower: tower_str.parse()?, children })
+    }
+}
+
+
+fn tree_node_fill(mut node: NodeMut<Tower>,
+                  towers: &mut HashMap<String, Tower>,
+                  parent: &HashMap<String, String>) -> Result<(), Error>
+{
+    let node_name = node.value().name.clone();
+    for (name, parent_name) in parent {
+        if *parent_name == node_name {
+            let tower = if let Some(t) = towers.remove(name) { t } else {
+                bail!("Tower '{}' is listed as child, but never defined", name)
+            };
+            let child_node = node.append(tower);
+            tree_node_fill(child_node, towers, parent)?;
+        }
+    }
+    Ok(())
+}
+
+
+fn build_tree(mut towers: HashMap<String, Tower>,
+              parent: HashMap<String, String>) -> Result<Tree<Tower>, Error>
+{
+    let root_name = if let Some(name) = towers.keys().find(|&key| !parent.contains_key(key)) {
+        name.to_string()
+    } else {
+        bail!("Cannot find the root tower")
+    };
+
+    let mut tree = Tree::new(towers.remove(&root_name).unwrap());
+    tree_node_fill(tree.root_mut(), &mut towers, &parent)?;
+    Ok(tree)
+}
+
+
+fn subtree_weight(node: NodeRef<Tower>) -> u32
+{
+    node.value().weight + node.children().fold(0, |sum, n| sum + subtree_weight(n))
+}
+
+
+fn find_unbalanced_node(node: NodeRef<Tower>) -> Option<(NodeRef<Tower>, u32)>
+{
+    if node.has_children() {
+        let mut max_index = 0;
+        let mut min_weight = u32::MAX;
+        let mut max_weight = u32::MIN;
+
+        for (index, weight) in node.children().map(subtree_weight).enumerate() {
+            if weight > max_weight {
+                max_weight = weight;
+                max_index = index;
+            }
+            if weight < min_weight {
+                min_weight = weight;
+            }
+        }
+
+        if min_weight != max_weight {
+            let node = node.children().nth(max_index).unwrap();
+            return Some((node, max_weight - min_weight));
+        }
+    }
+
+    None
+}
+
+
+pub struct Day07;
+
+impl Solution for Day07
+{
+    type Input = Tree<Tower>;
+    type Part1 = String;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let mut parent = HashMap::new();
+        let mut towers = HashMap::new();
+
+        for line in parse_lines::<TowerLine, _>(input.as_bytes())? {
+            for child in line.children {
+                parent.insert(child, line.tower.name.clone());
+            }
+            towers.insert(line.tower.name.clone(), line.tower);
+        }
+
+        build_tree(towers, parent)
+    }
+
+    fn part1(tree: &Self::Input) -> Result<String, Error>
+    {
+        Ok(tree.root().value().name.clone())
+    }
+
+    fn part2(tree: &Self::Input) -> Result<u32, Error>
+    {
+        for node in tree.root().traverse().filter_map(|item| match item {
+            Edge::Close(node) => Some(node),
+            Edge::Open(_) => None
+        }) {
+            if let Some((n, weight_diff)) = find_unbalanced_node(node) {
+                return Ok(n.value().weight - weight_diff);
+            }
+        }
+        bail!("The tower is balanced")
+    }
+}
@@ -0,0 +1,1211 @@
+//
+// day08.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use num::{ BigInt, ToPrimitive, Zero };
+use std::collections::{ BTreeSet, HashMap };
+use std::fmt;
+use std::io::{ BufRead, Write };
+use std::str::FromStr;
+use { parse_numbered_lines, LineError, Solution };
+
+
+/// Operand of an operation or a comparison: either a literal value, or
+/// the value of a register. Literals are stored with full precision, and
+/// converted to the type of the registers when used.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Operand
+{
+    Value(BigInt),
+    Register(String),
+}
+
+impl FromStr for Operand
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            Ok(Operand::Value(s.parse()?))
+        } else {
+            Ok(Operand::Register(parse_register(s)?))
+        }
+    }
+}
+
+impl fmt::Display for Operand
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Operand::Value(ref v) => write!(f, "{}", v),
+            Operand::Register(ref r) => f.write_str(r),
+        }
+    }
+}
+
+
+fn parse_register(s: &str) -> Result<String, Error>
+{
+    let valid = s.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+    ensure!(valid, "Invalid register name '{}'", s);
+    Ok(s.to_string())
+}
+
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Rel
+{
+    EQ,  // ==
+    NE,  // !=
+    GT,  // >
+    GE,  // >=
+    LT,  // <
+    LE,  // <=
+}
+
+impl FromStr for Rel
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        Ok(match s {
+            "==" => Rel::EQ,
+            "!=" => Rel::NE,
+            ">"  => Rel::GT,
+            ">=" => Rel::GE,
+            "<"  => Rel::LT,
+            "<=" => Rel::LE,
+            _ => bail!("Invalid relational operator '{}'", s),
+        })
+    }
+}
+
+impl fmt::Display for Rel
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Rel::EQ => "==",
+            Rel::NE => "!=",
+            Rel::GT => ">",
+            Rel::GE => ">=",
+            Rel::LT => "<",
+            Rel::LE => "<=",
+        })
+    }
+}
+
+
+/// Condition of an instruction. Compound conditions are written using the
+/// `not`, `and`, and `or` keywords, in decreasing order of precedence, e.g.
+/// `a > 1 and not b == c or d < 0`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Cond
+{
+    Cmp(Operand, Rel, Operand),
+    Not(Box<Cond>),
+    And(Box<Cond>, Box<Cond>),
+    Or(Box<Cond>, Box<Cond>),
+}
+
+impl Cond
+{
+    fn parse_or(tokens: &[&str], pos: &mut usize) -> Result<Self, Error> {
+        let mut cond = Self::parse_and(tokens, pos)?;
+        while tokens.get(*pos) == Some(&"or") {
+            *pos += 1;
+            cond = Cond::Or(Box::new(cond), Box::new(Self::parse_and(tokens, pos)?));
+        }
+        Ok(cond)
+    }
+
+    fn parse_and(tokens: &[&str], pos: &mut usize) -> Result<Self, Error> {
+        let mut cond = Self::parse_not(tokens, pos)?;
+        while tokens.get(*pos) == Some(&"and") {
+            *pos += 1;
+            cond = Cond::And(Box::new(cond), Box::new(Self::parse_not(tokens, pos)?));
+        }
+        Ok(cond)
+    }
+
+    fn parse_not(tokens: &[&str], pos: &mut usize) -> Result<Self, Error> {
+        // A register may be named "not", which is an operator only when
+        // it is not followed by a relational operator.
+        let operator = tokens.get(*pos + 1).is_some_and(|t| t.parse::<Rel>().is_err());
+        if tokens.get(*pos) == Some(&"not") && operator {
+            *pos += 1;
+            return Ok(Cond::Not(Box::new(Self::parse_not(tokens, pos)?)));
+        }
+        match tokens.get(*pos .. *pos + 3) {
+            Some(cmp) => {
+                *pos += 3;
+                Ok(Cond::Cmp(cmp[0].parse()?, cmp[1].parse()?, cmp[2].parse()?))
+            },
+            None => bail!("Incomplete comparison"),
+        }
+    }
+}
+
+impl FromStr for Cond
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        let mut pos = 0;
+        let cond = Self::parse_or(&tokens, &mut pos)
+            .map_err(|e| format_err!("Condition '{}': {}", s, e))?;
+        ensure!(pos == tokens.len(), "Condition '{}': unexpected '{}'", s, tokens[pos]);
+        Ok(cond)
+    }
+}
+
+impl fmt::Display for Cond
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Cond::Cmp(ref a, rel, ref b) => write!(f, "{} {} {}", a, rel, b),
+            Cond::Not(ref c) => write!(f, "not {}", c),
+            Cond::And(ref a, ref b) => write!(f, "{} and {}", a, b),
+            Cond::Or(ref a, ref b) => write!(f, "{} or {}", a, b),
+        }
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Op
+{
+    Inc(String, Operand),  // <name> inc <operand>
+    Dec(String, Operand),  // <name> dec <operand>
+    Set(String, Operand),  // <name> set <operand>
+    Mul(String, Operand),  // <name> mul <operand>
+    Div(String, Operand),  // <name> div <operand>
+    Mod(String, Operand),  // <name> mod <operand>
+}
+
+impl Op
+{
+    /// Register modified by the operation.
+    pub fn register(&self) -> &str {
+        match *self {
+            Op::Inc(ref r, _) | Op::Dec(ref r, _) | Op::Set(ref r, _) |
+            Op::Mul(ref r, _) | Op::Div(ref r, _) | Op::Mod(ref r, _) => r,
+        }
+    }
+
+    pub fn operand(&self) -> &Operand {
+        match *self {
+            Op::Inc(_, ref v) | Op::Dec(_, ref v) | Op::Set(_, ref v) |
+            Op::Mul(_, ref v) | Op::Div(_, ref v) | Op::Mod(_, ref v) => v,
+        }
+    }
+}
+
+impl FromStr for Op
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let tokens: Vec<_> = s.split_whitespace().collect();
+        ensure!(tokens.len() == 3, "Instruction '{}' is not '<register> <operation> <operand>'", s);
+        let regname = parse_register(tokens[0])?;
+        let operand = tokens[2].parse()?;
+        Ok(match tokens[1] {
+            "inc" => Op::Inc(regname, operand),
+            "dec" => Op::Dec(regname, operand),
+            "set" => Op::Set(regname, operand),
+            "mul" => Op::Mul(regname, operand),
+            "div" => Op::Div(regname, operand),
+            "mod" => Op::Mod(regname, operand),
+            _ => bail!("Instruction '{}' contains invalid operation", s),
+        })
+    }
+}
+
+impl fmt::Display for Op
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            Op::Inc(..) => "inc",
+            Op::Dec(..) => "dec",
+            Op::Set(..) => "set",
+            Op::Mul(..) => "mul",
+            Op::Div(..) => "div",
+            Op::Mod(..) => "mod",
+        };
+        write!(f, "{} {} {}", self.register(), name, self.operand())
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instr
+{
+    pub op: Op,
+    pub cond: Cond,
+}
+
+impl FromStr for Instr
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let if_pos = if let Some(pos) = s.find(" if ") { pos } else {
+            bail!("Input '{}' does not contain 'if'", s);
+        };
+        let (op_str, cond_str) = s.split_at(if_pos);
+        Ok(Instr {
+            op: op_str.trim().parse()?,
+            cond: cond_str[4..].trim().parse()?,
+        })
+    }
+}
+
+impl fmt::Display for Instr
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} if {}", self.op, self.cond)
+    }
+}
+
+
+/// Parsed list of instructions, along with the line numbers of the source
+/// from which each one was parsed.
+#[derive(Debug, Clone, Default)]
+pub struct Program
+{
+    instrs: Vec<Instr>,
+    lines: Vec<usize>,
+}
+
+impl Program
+{
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.instrs.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.instrs.is_empty()
+    }
+
+    #[inline]
+    pub fn instructions(&self) -> &[Instr] {
+        &self.instrs
+    }
+
+    /// Returns an instruction, along with its line number.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<(usize, &Instr)> {
+        self.instrs.get(index).map(|instr| (self.lines[index], instr))
+    }
+
+    /// Iterates over the instructions, along with their line numbers.
+    pub fn iter(&self) -> impl Iterator<Item=(usize, &Instr)> {
+        self.lines.iter().cloned().zip(&self.instrs)
+    }
+}
+
+impl fmt::Display for Program
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for instr in &self.instrs {
+            writeln!(f, "{}", instr)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Program
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let (lines, instrs) = parse_numbered_lines(s.as_bytes())?.into_iter().unzip();
+        Ok(Program { instrs, lines })
+    }
+}
+
+
+/// Type of the values stored in registers. Conversion from literals and
+/// arithmetic operations return `None` when the value does not fit, and
+/// the divisor is never zero.
+pub trait RegisterValue: Clone + Ord + fmt::Display + fmt::Debug
+{
+    fn from_i32(value: i32) -> Self;
+    fn from_literal(value: &BigInt) -> Option<Self>;
+    fn is_zero(&self) -> bool;
+    fn add(&self, other: &Self) -> Option<Self>;
+    fn sub(&self, other: &Self) -> Option<Self>;
+    fn mul(&self, other: &Self) -> Option<Self>;
+    fn div(&self, other: &Self) -> Option<Self>;
+    fn rem(&self, other: &Self) -> Option<Self>;
+}
+
+
+// Plain integers wrap around on overflow, like in a release build.
+macro_rules! wrapping_register_value {
+    ($t:ty, $to:ident) => {
+        impl RegisterValue for $t
+        {
+            #[inline] fn from_i32(value: i32) -> Self { value as $t }
+            #[inline] fn from_literal(value: &BigInt) -> Option<Self> { value.$to() }
+            #[inline] fn is_zero(&self) -> bool { *self == 0 }
+            #[inline] fn add(&self, other: &Self) -> Option<Self> { Some(self.wrapping_add(*other)) }
+            #[inline] fn sub(&self, other: &Self) -> Option<Self> { Some(self.wrapping_sub(*other)) }
+            #[inline] fn mul(&self, other: &Self) -> Option<Self> { Some(self.wrapping_mul(*other)) }
+            #[inline] fn div(&self, other: &Self) -> Option<Self> { Some(self.wrapping_div(*other)) }
+            #[inline] fn rem(&self, other: &Self) -> Option<Self> { Some(self.wrapping_rem(*other)) }
+        }
+    }
+}
+
+wrapping_register_value!(i32, to_i32);
+wrapping_register_value!(i64, to_i64);
+
+
+/// 32-bit register value for which overflows are reported as errors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Checked(pub i32);
+
+impl fmt::Display for Checked
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl RegisterValue for Checked
+{
+    #[inline] fn from_i32(value: i32) -> Self { Checked(value) }
+    #[inline] fn from_literal(value: &BigInt) -> Option<Self> { value.to_i32().map(Checked) }
+    #[inline] fn is_zero(&self) -> bool { self.0 == 0 }
+    #[inline] fn add(&self, other: &Self) -> Option<Self> { self.0.checked_add(other.0).map(Checked) }
+    #[inline] fn sub(&self, other: &Self) -> Option<Self> { self.0.checked_sub(other.0).map(Checked) }
+    #[inline] fn mul(&self, other: &Self) -> Option<Self> { self.0.checked_mul(other.0).map(Checked) }
+    #[inline] fn div(&self, other: &Self) -> Option<Self> { self.0.checked_div(other.0).map(Checked) }
+    #[inline] fn rem(&self, other: &Self) -> Option<Self> { self.0.checked_rem(other.0).map(Checked) }
+}
+
+
+impl RegisterValue for BigInt
+{
+    #[inline] fn from_i32(value: i32) -> Self { BigInt::from(value) }
+    #[inline] fn from_literal(value: &BigInt) -> Option<Self> { Some(value.clone()) }
+    #[inline] fn is_zero(&self) -> bool { Zero::is_zero(self) }
+    #[inline] fn add(&self, other: &Self) -> Option<Self> { Some(self + other) }
+    #[inline] fn sub(&self, other: &Self) -> Option<Self> { Some(self - other) }
+    #[inline] fn mul(&self, other: &Self) -> Option<Self> { Some(self * other) }
+    #[inline] fn div(&self, other: &Self) -> Option<Self> { Some(self / other) }
+    #[inline] fn rem(&self, other: &Self) -> Option<Self> { Some(self % other) }
+}
+
+
+/// Largest value held by a register at any time during a run, along with
+/// the register, and the line of the instruction which stored the value.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Peak<V>
+{
+    pub value: V,
+    pub register: String,
+    pub line: usize,
+}
+
+
+/// Change of the value of a register made by an instruction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change<V>
+{
+    pub register: String,
+    pub old: V,
+    pub new: V,
+}
+
+
+/// Outcome of executing an instruction: the value of its condition, and
+/// the change made to a register if the condition held.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<V>
+{
+    pub line: usize,
+    pub condition: bool,
+    pub change: Option<Change<V>>,
+}
+
+impl<V: fmt::Display> fmt::Display for Step<V>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.change {
+            Some(ref c) => write!(f, "{:5}  {}: {} -> {}", self.condition, c.register, c.old, c.new),
+            None => write!(f, "{:5}  skipped", self.condition),
+        }
+    }
+}
+
+
+/// Register machine, generic over the type of the register values.
+#[derive(Debug, Clone, Default)]
+pub struct Machine<V: RegisterValue = i32>
+{
+    regs: HashMap<String, V>,
+    peak: Option<Peak<V>>,
+}
+
+
+impl<V: RegisterValue> Machine<V>
+{
+    pub fn new() -> Self {
+        Machine {
+            regs: HashMap::new(),
+            peak: None,
+        }
+    }
+
+    /// Value of a register. Registers which have not been written to
+    /// have a value of zero.
+    #[inline]
+    pub fn get(&self, regname: &str) -> V {
+        match self.regs.get(regname) {
+            Some(value) => value.clone(),
+            None => V::from_i32(0),
+        }
+    }
+
+    /// Registers written to by the instructions executed so far.
+    #[inline]
+    pub fn registers(&self) -> &HashMap<String, V> {
+        &self.regs
+    }
+
+    /// Largest value among the registers written to, if any.
+    pub fn max_value(&self) -> Option<&V> {
+        self.regs.values().max()
+    }
+
+    /// Largest value held by a register since the machine was created.
+    #[inline]
+    pub fn peak(&self) -> Option<&Peak<V>> {
+        self.peak.as_ref()
+    }
+
+    #[inline]
+    pub fn value(&self, operand: &Operand) -> Result<V, Error> {
+        match *operand {
+            Operand::Value(ref v) => match V::from_literal(v) {
+                Some(value) => Ok(value),
+                None => bail!("Literal {} is out of range", v),
+            },
+            Operand::Register(ref r) => Ok(self.get(r)),
+        }
+    }
+
+    pub fn check_condition(&self, cond: &Cond) -> Result<bool, Error> {
+        Ok(match *cond {
+            Cond::Cmp(ref a, rel, ref b) => {
+                let (a, b) = (self.value(a)?, self.value(b)?);
+                match rel {
+                    Rel::EQ => a == b,
+                    Rel::NE => a != b,
+                    Rel::GT => a >  b,
+                    Rel::GE => a >= b,
+                    Rel::LT => a <  b,
+                    Rel::LE => a <= b,
+                }
+            },
+            Cond::Not(ref c) => !self.check_condition(c)?,
+            Cond::And(ref a, ref b) => self.check_condition(a)? && self.check_condition(b)?,
+            Cond::Or(ref a, ref b) => self.check_condition(a)? || self.check_condition(b)?,
+        })
+    }
+
+    /// Executes an instruction from a given line, returning whether its
+    /// condition held and the change made, if any. Literals out of range,
+    /// division by zero, and overflows are reported as a `LineError`.
+    pub fn execute(&mut self, line: usize, ins: &Instr) -> Result<Step<V>, Error> {
+        let line_error = |error| -> Error { LineError { name: None, line, error }.into() };
+        if !self.check_condition(&ins.cond).map_err(line_error)? {
+            return Ok(Step { line, condition: false, change: None });
+        }
+        let regname = ins.op.register();
+        let current = self.get(regname);
+        let operand = self.value(ins.op.operand()).map_err(line_error)?;
+        let value = match ins.op {
+            Op::Div(..) | Op::Mod(..) if operand.is_zero() => {
+                return Err(line_error(format_err!("Division by zero")));
+            },
+            Op::Inc(..) => current.add(&operand),
+            Op::Dec(..) => current.sub(&operand),
+            Op::Set(..) => Some(operand),
+            Op::Mul(..) => current.mul(&operand),
+            Op::Div(..) => current.div(&operand),
+            Op::Mod(..) => current.rem(&operand),
+        };
+        let value = match value {
+            Some(value) => value,
+            None => return Err(line_error(format_err!("Overflow in '{}' with {} = {}",
+                                                      ins.op, regname, current))),
+        };
+        self.regs.insert(regname.to_string(), value.clone());
+        if self.peak.as_ref().is_none_or(|peak| value > peak.value) {
+            self.peak = Some(Peak { value: value.clone(), register: regname.to_string(), line });
+        }
+        Ok(Step {
+            line,
+            condition: true,
+            change: Some(Change { register: regname.to_string(), old: current, new: value }),
+        })
+    }
+
+    pub fn run(&mut self, program: &Program) -> Result<(), Error> {
+        for (line, instr) in program.iter() {
+            self.execute(line, instr)?;
+        }
+        Ok(())
+    }
+
+    /// Same as `run`, but writes a line to the output for each instruction
+    /// executed, with the value of its condition and the change made.
+    pub fn trace<W: Write>(&mut self, program: &Program, mut output: W) -> Result<(), Error> {
+        for (line, instr) in program.iter() {
+            let step = self.execute(line, instr)?;
+            writeln!(output, "{}", trace_line(instr, &step))?;
+        }
+        Ok(())
+    }
+}
+
+impl<V: RegisterValue> fmt::Display for Machine<V>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result
+    {
+        let mut regs: Vec<_> = self.regs.iter().collect();
+        regs.sort();
+        for (regname, value) in regs {
+            writeln!(f, "{}: {}", regname, value)?;
+        }
+        Ok(())
+    }
+}
+
+
+fn trace_line<V: fmt::Display>(instr: &Instr, step: &Step<V>) -> String
+{
+    format!("{:4}  {:32}  {}", step.line, instr.to_string(), step)
+}
+
+
+/// Why the debugger stopped running the program.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stop<V>
+{
+    Breakpoint(usize),
+    Watchpoint(Change<V>),
+    Finished,
+}
+
+
+/// Runs a program step by step, stopping at breakpoints set on lines (before
+/// the instruction in the line is executed) and on registers (after their
+/// value changes).
+pub struct Debugger<'a, V: RegisterValue = i32>
+{
+    program: &'a Program,
+    machine: Machine<V>,
+    next: usize,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeSet<String>,
+    at_breakpoint: bool,
+}
+
+impl<'a, V: RegisterValue> Debugger<'a, V>
+{
+    pub fn new(program: &'a Program) -> Self {
+        Debugger {
+            program,
+            machine: Machine::new(),
+            next: 0,
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            at_breakpoint: false,
+        }
+    }
+
+    #[inline]
+    pub fn machine(&self) -> &Machine<V> {
+        &self.machine
+    }
+
+    #[inline]
+    pub fn finished(&self) -> bool {
+        self.next >= self.program.len()
+    }
+
+    /// Line number of the next instruction to execute, if any.
+    pub fn next_line(&self) -> Option<usize> {
+        self.program.get(self.next).map(|(line, _)| line)
+    }
+
+    pub fn add_breakpoint(&mut self, line: usize) {
+        self.breakpoints.insert(line);
+    }
+
+    pub fn add_watchpoint(&mut self, regname: &str) {
+        self.watchpoints.insert(regname.to_string());
+    }
+
+    /// Removes all the breakpoints and watchpoints.
+    pub fn clear(&mut self) {
+        self.breakpoints.clear();
+        self.watchpoints.clear();
+    }
+
+    /// Executes the next instruction, returning it along with the outcome,
+    /// or `None` if the program has finished.
+    pub fn step(&mut self) -> Result<Option<(&'a Instr, Step<V>)>, Error> {
+        let (line, instr) = match self.program.get(self.next) {
+            Some(next) => next,
+            None => return Ok(None),
+        };
+        let step = self.machine.execute(line, instr)?;
+        self.next += 1;
+        self.at_breakpoint = false;
+        Ok(Some((instr, step)))
+    }
+
+    /// Executes instructions until the program finishes, or a breakpoint
+    /// or watchpoint is hit. Breakpoints are checked before executing each
+    /// instruction, except for the one at which the previous call stopped,
+    /// so it is possible to continue after stopping at a breakpoint.
+    pub fn resume(&mut self) -> Result<Stop<V>, Error> {
+        while let Some(line) = self.next_line() {
+            if self.breakpoints.contains(&line) && !self.at_breakpoint {
+                self.at_breakpoint = true;
+                return Ok(Stop::Breakpoint(line));
+            }
+            if let Some((_, Step { change: Some(change), .. })) = self.step()? {
+                if change.old != change.new && self.watchpoints.contains(&change.register) {
+                    return Ok(Stop::Watchpoint(change));
+                }
+            }
+        }
+        Ok(Stop::Finished)
+    }
+
+    /// Reads debugger commands from the input, and writes their results
+    /// to the output, until the input ends or the `quit` command is used.
+    pub fn interact<R: BufRead, W: Write>(&mut self, input: R, mut output: W) -> Result<(), Error> {
+        writeln!(output, "{} instructions loaded, type 'help' for a list of commands",
+                 self.program.len())?;
+        for line in input.lines() {
+            let line = line?;
+            let words: Vec<_> = line.split_whitespace().collect();
+            match self.command(&words, &mut output) {
+                Ok(true) => break,
+                Ok(false) => (),
+                Err(e) => writeln!(output, "error: {}", e)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Runs a debugger command, returning whether the debugger should exit.
+    fn command<W: Write>(&mut self, words: &[&str], output: &mut W) -> Result<bool, Error> {
+        match (words.first().cloned().unwrap_or(""), words.get(1)) {
+            ("", _) => (),
+            ("s", None) | ("step", None) => match self.step()? {
+                Some((instr, step)) => writeln!(output, "{}", trace_line(instr, &step))?,
+                None => writeln!(output, "program finished")?,
+            },
+            ("c", None) | ("continue", None) => match self.resume()? {
+                Stop::Breakpoint(line) => writeln!(output, "breakpoint at line {}", line)?,
+                Stop::Watchpoint(c) => writeln!(output, "watchpoint: {} changed from {} to {}",
+                                                c.register, c.old, c.new)?,
+                Stop::Finished => writeln!(output, "program finished")?,
+            },
+            ("b", Some(arg)) | ("break", Some(arg)) => match arg.parse() {
+                Ok(line) => {
+                    self.add_breakpoint(line);
+                    writeln!(output, "breakpoint at line {}", line)?;
+                },
+                Err(_) => {
+                    let regname = parse_register(arg)?;
+                    self.add_watchpoint(&regname);
+                    writeln!(output, "watchpoint on register {}", regname)?;
+                },
+            },
+            ("d", None) | ("delete", None) => self.clear(),
+            ("p", Some(regname)) | ("print", Some(regname)) => {
+                writeln!(output, "{}: {}", regname, self.machine.get(regname))?;
+            },
+            ("p", None) | ("print", None) => write!(output, "{}", self.machine)?,
+            ("l", None) | ("list", None) => match self.program.get(self.next) {
+                Some((line, instr)) => writeln!(output, "{:4}  {}", line, instr)?,
+                None => writeln!(output, "program finished")?,
+            },
+            ("q", None) | ("quit", None) => return Ok(true),
+            ("h", None) | ("help", None) => writeln!(output, "{}", DEBUGGER_HELP)?,
+            _ => bail!("invalid command '{}', type 'help' for a list of commands", words.join(" ")),
+        }
+        Ok(false)
+    }
+}
+
+
+const DEBUGGER_HELP: &str = "\
+step, s              Execute the next instruction.
+continue, c          Execute instructions until a breakpoint or watchpoint is hit.
+break, b <line>      Stop before executing the instruction in a line.
+break, b <register>  Stop after the value of a register changes.
+delete, d            Remove all breakpoints and watchpoints.
+print, p [register]  Print the value of a register, or all of them.
+list, l              Show the next instruction.
+quit, q              Exit the debugger.";
+
+
+/// Report of the static analysis of a program, see `analyze()`. Registers
+/// are sorted by name, and instructions are identified by line number.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Analysis
+{
+    /// Registers used in conditions or as operands, but never modified.
+    pub read_only: BTreeSet<String>,
+    /// Registers modified, but never used in conditions or as operands.
+    pub write_only: BTreeSet<String>,
+    /// Registers whose value never reaches a condition, either directly
+    /// or as the operand of an operation on another register.
+    pub unconditional: BTreeSet<String>,
+    /// Lines with instructions whose condition can never hold.
+    pub dead: Vec<usize>,
+}
+
+impl fmt::Display for Analysis
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_list(f, "only read", &self.read_only)?;
+        write_list(f, "only written", &self.write_only)?;
+        write_list(f, "never affect a condition", &self.unconditional)?;
+        write_list(f, "dead instructions (lines)", &self.dead)
+    }
+}
+
+
+fn write_list<I>(f: &mut fmt::Formatter, title: &str, items: I) -> fmt::Result
+    where I: IntoIterator, I::Item: fmt::Display
+{
+    let items: Vec<_> = items.into_iter().map(|item| item.to_string()).collect();
+    if items.is_empty() {
+        writeln!(f, "{}: none", title)
+    } else {
+        writeln!(f, "{}: {}", title, items.join(", "))
+    }
+}
+
+
+/// Values known during the analysis, for the registers written so far.
+/// `None` is used for values which depend on the type of the registers,
+/// which happens after an overflow of a 32-bit value or with literals out
+/// of its range, or which cannot be known because an instruction may or
+/// may not have been executed.
+type KnownValues<'a> = HashMap<&'a str, Option<i64>>;
+
+fn known_operand(values: &KnownValues, operand: &Operand) -> Option<i64>
+{
+    match *operand {
+        Operand::Value(ref v) => v.to_i32().map(i64::from),
+        Operand::Register(ref r) => *values.get(r.as_str()).unwrap_or(&Some(0)),
+    }
+}
+
+/// Whether a condition can be evaluated with any type of registers. Those
+/// with literals out of the range of a 32-bit value may fail to evaluate.
+fn fits_i32(cond: &Cond) -> bool
+{
+    match *cond {
+        Cond::Cmp(ref a, _, ref b) => [a, b].iter().all(|operand| match **operand {
+            Operand::Value(ref v) => v.to_i32().is_some(),
+            Operand::Register(_) => true,
+        }),
+        Cond::Not(ref c) => fits_i32(c),
+        Cond::And(ref a, ref b) | Cond::Or(ref a, ref b) => fits_i32(a) && fits_i32(b),
+    }
+}
+
+/// Evaluates a condition if its value does not depend on unknown values.
+fn known_condition(values: &KnownValues, cond: &Cond) -> Option<bool>
+{
+    if !fits_i32(cond) {
+        return None;
+    }
+    match *cond {
+        Cond::Cmp(ref a, rel, ref b) => {
+            let (a, b) = match (known_operand(values, a), known_operand(values, b)) {
+                (Some(a), Some(b)) => (a, b),
+                _ if a == b => (0, 0),  // A register compared with itself.
+                _ => return None,
+            };
+            Some(match rel {
+                Rel::EQ => a == b,
+                Rel::NE => a != b,
+                Rel::GT => a > b,
+                Rel::GE => a >= b,
+                Rel::LT => a < b,
+                Rel::LE => a <= b,
+            })
+        },
+        Cond::Not(ref c) => known_condition(values, c).map(|c| !c),
+        Cond::And(ref a, ref b) => match (known_condition(values, a), known_condition(values, b)) {
+            (Some(false), _) | (_, Some(false)) => Some(false),
+            (Some(true), Some(true)) => Some(true),
+            _ => None,
+        },
+        Cond::Or(ref a, ref b) => match (known_condition(values, a), known_condition(values, b)) {
+            (Some(true), _) | (_, Some(true)) => Some(true),
+            (Some(false), Some(false)) => Some(false),
+            _ => None,
+        },
+    }
+}
+
+/// Result of an operation, if it is the same for all register types.
+fn known_result(op: &Op, current: i64, operand: i64) -> Option<i64>
+{
+    let value = match *op {
+        Op::Inc(..) => current + operand,
+        Op::Dec(..) => current - operand,
+        Op::Set(..) => operand,
+        Op::Mul(..) => current * operand,
+        Op::Div(..) | Op::Mod(..) if operand == 0 => return None,
+        Op::Div(..) => current / operand,
+        Op::Mod(..) => current % operand,
+    };
+    if value < i64::from(i32::MIN) || value > i64::from(i32::MAX) {
+        None
+    } else {
+        Some(value)
+    }
+}
+
+
+/// Analyzes a program without running it. Registers are read and written
+/// by looking at the instructions, and the values they hold are followed
+/// as long as they can be known, which allows finding instructions whose
+/// condition never holds. The results are valid for all register types.
+pub fn analyze(program: &Program) -> Analysis
+{
+    let mut read = BTreeSet::new();
+    let mut written = BTreeSet::new();
+    let mut conditional = BTreeSet::new();
+    for instr in program.instructions() {
+        written.insert(instr.op.register());
+        if let Operand::Register(ref r) = *instr.op.operand() {
+            read.insert(r.as_str());
+        }
+        let mut conds = vec![&instr.cond];
+        while let Some(cond) = conds.pop() {
+            match *cond {
+                Cond::Cmp(ref a, _, ref b) => {
+                    for operand in &[a, b] {
+                        if let Operand::Register(ref r) = **operand {
+                            read.insert(r.as_str());
+                            conditional.insert(r.as_str());
+                        }
+                    }
+                },
+                Cond::Not(ref c) => conds.push(c),
+                Cond::And(ref a, ref b) | Cond::Or(ref a, ref b) => {
+                    conds.push(a);
+                    conds.push(b);
+                },
+            }
+        }
+    }
+
+    // Operands of operations on registers which affect a condition, in
+    // turn affect it as well. Repeat until no more registers are found.
+    loop {
+        let found: Vec<_> = program.instructions().iter()
+            .filter(|instr| conditional.contains(instr.op.register()))
+            .filter_map(|instr| match *instr.op.operand() {
+                Operand::Register(ref r) if !conditional.contains(r.as_str()) => Some(r.as_str()),
+                _ => None,
+            })
+            .collect();
+        if found.is_empty() {
+            break;
+        }
+        conditional.extend(found);
+    }
+
+    let mut values = KnownValues::new();
+    let mut dead = Vec::new();
+    for (line, instr) in program.iter() {
+        let regname = instr.op.register();
+        let current = known_operand(&values, &Operand::Register(regname.to_string()));
+        let result = match (current, known_operand(&values, instr.op.operand())) {
+            (Some(current), Some(operand)) => known_result(&instr.op, current, operand),
+            _ => None,
+        };
+        let value = match known_condition(&values, &instr.cond) {
+            Some(false) => {
+                dead.push(line);
+                continue;
+            },
+            Some(true) => result,
+            None if result == current => result,
+            None => None,
+        };
+        values.insert(regname, value);
+    }
+
+    let strings = |set: BTreeSet<&str>| set.into_iter().map(String::from).collect();
+    Analysis {
+        read_only: strings(read.difference(&written).cloned().collect()),
+        write_only: strings(written.difference(&read).cloned().collect()),
+        unconditional: strings(read.union(&written).filter(|r| !conditional.contains(*r)).cloned().collect()),
+        dead,
+    }
+}
+
+
+/// Removes the instructions whose condition never holds. The resulting
+/// program produces the same register values, and keeps the line numbers
+/// of the original one.
+pub fn eliminate_dead(program: &Program) -> Program
+{
+    let dead: BTreeSet<_> = analyze(program).dead.into_iter().collect();
+    let (lines, instrs) = program.iter()
+        .filter(|&(line, _)| !dead.contains(&line))
+        .map(|(line, instr)| (line, instr.clone()))
+        .unzip();
+    Program { instrs, lines }
+}
+
+
+fn run_program(program: &Program) -> Result<Machine<Checked>, Error>
+{
+    let mut m = Machine::new();
+    m.run(program)?;
+    Ok(m)
+}
+
+
+pub struct Day08;
+
+impl Solution for Day08
+{
+    type Input = Program;
+    type Part1 = i32;
+    type Part2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        input.parse()
+    }
+
+    fn part1(program: &Self::Input) -> Result<i32, Error>
+    {
+        match run_program(program)?.max_value() {
+            Some(value) => Ok(value.0),
+            None => bail!("No register was written to"),
+        }
+    }
+
+    fn part2(program: &Self::Input) -> Result<i32, Error>
+    {
+        match run_program(program)?.peak() {
+            Some(peak) => Ok(peak.value.0),
+            None => bail!("No register was written to"),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn cmp(a: &str, rel: Rel, b: &str) -> Cond {
+        Cond::Cmp(a.parse().unwrap(), rel, b.parse().unwrap())
+    }
+
+    #[test]
+    fn parse_old_format() {
+        let instr: Instr = "b inc 5 if a > 1".parse().unwrap();
+        assert_eq!(instr.op, Op::Inc("b".to_string(), Operand::Value(BigInt::from(5))));
+        assert_eq!(instr.cond, cmp("a", Rel::GT, "1"));
+        let instr: Instr = "c dec -10 if a >= 1".parse().unwrap();
+        assert_eq!(instr.op, Op::Dec("c".to_string(), Operand::Value(BigInt::from(-10))));
+        assert_eq!(instr.cond, cmp("a", Rel::GE, "1"));
+    }
+
+    #[test]
+    fn parse_register_operands() {
+        let instr: Instr = "a mul b if c != d".parse().unwrap();
+        assert_eq!(instr.op, Op::Mul("a".to_string(), Operand::Register("b".to_string())));
+        assert_eq!(instr.cond, cmp("c", Rel::NE, "d"));
+    }
+
+    #[test]
+    fn parse_precedence() {
+        let cond: Cond = "not a > 1 and b < 2 or c == 3".parse().unwrap();
+        assert_eq!(cond, Cond::Or(
+            Box::new(Cond::And(Box::new(Cond::Not(Box::new(cmp("a", Rel::GT, "1")))),
+                               Box::new(cmp("b", Rel::LT, "2")))),
+            Box::new(cmp("c", Rel::EQ, "3"))));
+        let cond: Cond = "a == 1 or b == 2 and not c == 3".parse().unwrap();
+        assert_eq!(cond, Cond::Or(
+            Box::new(cmp("a", Rel::EQ, "1")),
+            Box::new(Cond::And(Box::new(cmp("b", Rel::EQ, "2")),
+                               Box::new(Cond::Not(Box::new(cmp("c", Rel::EQ, "3"))))))));
+        assert_eq!(cond.to_string(), "a == 1 or b == 2 and not c == 3");
+    }
+
+    #[test]
+    fn parse_keyword_registers() {
+        let instr: Instr = "not inc 1 if not == 0".parse().unwrap();
+        assert_eq!(instr.op.register(), "not");
+        assert_eq!(instr.cond, cmp("not", Rel::EQ, "0"));
+        let cond: Cond = "not not < 0".parse().unwrap();
+        assert_eq!(cond, Cond::Not(Box::new(cmp("not", Rel::LT, "0"))));
+        assert!("not a".parse::<Cond>().is_err());
+        assert!("a > 1 and".parse::<Cond>().is_err());
+    }
+
+    fn debug(source: &str, script: &str) -> String {
+        let program: Program = source.parse().unwrap();
+        let mut output = Vec::new();
+        Debugger::<i32>::new(&program).interact(script.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn debugger_breakpoint_first_line() {
+        let output = debug("a inc 1 if b == 0\nb inc 2 if a > 0\n", "b 1\nc\np\nc\np\nc\n");
+        assert_eq!(output.lines().skip(1).collect::<Vec<_>>(), [
+            "breakpoint at line 1",
+            "breakpoint at line 1",
+            "program finished",
+            "a: 1",
+            "b: 2",
+            "program finished",
+        ]);
+    }
+
+    #[test]
+    fn debugger_watchpoint_before_breakpoint() {
+        let source = "a inc 1 if a == 0\nb inc 1 if a == 1\nc inc 1 if b == 1\n";
+        let output = debug(source, "b a\nb 2\nc\nc\nc\np\n");
+        assert_eq!(output.lines().skip(1).collect::<Vec<_>>(), [
+            "watchpoint on register a",
+            "breakpoint at line 2",
+            "watchpoint: a changed from 0 to 1",
+            "breakpoint at line 2",
+            "program finished",
+            "a: 1",
+            "b: 1",
+            "c: 1",
+        ]);
+    }
+
+    #[test]
+    fn wide_literals() {
+        let program: Program = "a inc 3000000000 if b == 0\nb set a if a > 2999999999\n".parse().unwrap();
+        let mut m = Machine::<i64>::new();
+        m.run(&program).unwrap();
+        assert_eq!(m.get("b"), 3000000000);
+        let mut m = Machine::<BigInt>::new();
+        m.run(&program).unwrap();
+        assert_eq!(m.get("b"), BigInt::from(3000000000u32));
+        let e = Machine::<Checked>::new().run(&program).unwrap_err();
+        assert_eq!(e.to_string(), "line 1: Literal 3000000000 is out of range");
+        assert!("a inc 1_0 if b == 0".parse::<Instr>().is_err());
+    }
+
+    const EXAMPLE: &str = "b inc 5 if a > 1\na inc 1 if b < 5\nc dec -10 if a >= 1\nc inc -20 if c == 10\n";
+
+    const ANALYZED: &str = "\
+a inc 5 if 1 > 2
+b inc 3 if x == 0
+c inc b if a < 0
+d set 7 if c == c
+e mul 2 if d > 100 or y != 0
+f inc d if not b == 3
+b inc z if b > 0
+";
+
+    fn names(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn assert_equivalent<V: RegisterValue + PartialEq>(program: &Program) {
+        let eliminated = eliminate_dead(program);
+        let (mut m, mut e) = (Machine::<V>::new(), Machine::<V>::new());
+        m.run(program).unwrap();
+        e.run(&eliminated).unwrap();
+        assert_eq!(m.registers(), e.registers());
+        assert_eq!(m.peak(), e.peak());
+    }
+
+    #[test]
+    fn analyze_registers() {
+        let analysis = analyze(&ANALYZED.parse().unwrap());
+        assert_eq!(analysis.read_only, names(&["x", "y", "z"]));
+        assert_eq!(analysis.write_only, names(&["e", "f"]));
+        assert_eq!(analysis.unconditional, names(&["e", "f"]));
+        assert_eq!(analysis.dead, [1, 3, 5, 6]);
+    }
+
+    #[test]
+    fn analyze_example() {
+        let program: Program = EXAMPLE.parse().unwrap();
+        let analysis = analyze(&program);
+        assert!(analysis.read_only.is_empty());
+        assert!(analysis.write_only.is_empty());
+        assert!(analysis.unconditional.is_empty());
+        assert_eq!(analysis.dead, [1]);
+        assert_eq!(eliminate_dead(&program).to_string(),
+                   "a inc 1 if b < 5\nc dec -10 if a >= 1\nc inc -20 if c == 10\n");
+    }
+
+    #[test]
+    fn analyze_unknown_values() {
+        // The value of "u" is unknown after an overflow of a 32-bit value,
+        // and after it the value of "a" is unknown as well when it is set
+        // by an instruction which may or may not be executed.
+        let unknown = "u inc 2147483647 if u == 0\nu inc 1 if u > 0\n";
+        let dead = |source: &str| analyze(&format!("{}{}", unknown, source).parse().unwrap()).dead;
+        assert_eq!(dead("a inc 1 if u > 0\nb inc 1 if u < 0\n"), []);
+        assert_eq!(dead("a inc 1 if u > 0\nb inc 1 if a == 0\nc inc 1 if a == 2\n"), []);
+        assert_eq!(dead("a inc 1 if b == 0\nb inc 1 if a == 0\n"), [4]);
+        // Unless the instruction would not change its value.
+        assert_eq!(dead("a inc 0 if u > 0\nb inc 1 if a != 0\n"), [4]);
+        assert_eq!(dead("a set 3 if a == 0\na set 3 if u > 0\nb inc 1 if a != 3\n"), [5]);
+        // Comparing a register with itself needs no value.
+        assert_eq!(dead("b inc 1 if u != u\nc inc 1 if not u <= u\n"), [3, 4]);
+        // Literals out of the range of a 32-bit value may fail to evaluate.
+        assert_eq!(dead("a inc 1 if b < 3000000000 and 1 > 2\n"), []);
+    }
+
+    #[test]
+    fn eliminate_equivalent() {
+        for source in &[EXAMPLE, ANALYZED] {
+            let program: Program = source.parse().unwrap();
+            assert!(eliminate_dead(&program).len() < program.len());
+            assert_equivalent::<Checked>(&program);
+            assert_equivalent::<BigInt>(&program);
+        }
+    }
+}
@@ -0,0 +1,77 @@
+//
+// day09.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use Solution;
+
+
+#[derive(Debug, Default)]
+struct StreamStats
+{
+    nchars: u32,
+    score: u32,
+}
+
+
+fn scan_stream(input: &str) -> Result<StreamStats, Error>
+{
+    let mut stats = StreamStats::default();
+    let mut in_comment = false;
+    let mut skip_next = false;
+    let mut score_stack = vec![0];
+
+    for ch in input.bytes() {
+        if skip_next {
+            skip_next = false;
+            continue;
+        }
+        match ch {
+            b'{' if !in_comment => {
+                // Score for a group is "one more than the score of the
+                // group that contains it".
+                let current_score = score_stack.last().unwrap() + 1;
+                score_stack.push(current_score);
+            },
+            b'}' if !in_comment => {
+                if score_stack.len() < 2 {
+                    bail!("Unbalanced closing brace in stream");
+                }
+                stats.score += score_stack.pop().unwrap();
+            },
+            b'!' => skip_next = true,
+            b'<' if !in_comment => in_comment = true,
+            b'>' if in_comment => in_comment = false,
+            _ if in_comment => stats.nchars += 1,
+            _ => (),  // Do nothing.
+        };
+    }
+    Ok(stats)
+}
+
+
+pub struct Day09;
+
+impl Solution for Day09
+{
+    type Input = String;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(input.to_string())
+    }
+
+    fn part1(stream: &Self::Input) -> Result<u32, Error>
+    {
+        Ok(scan_stream(stream)?.score)
+    }
+
+    fn part2(stream: &Self::Input) -> Result<u32, Error>
+    {
+        Ok(scan_stream(stream)?.nchars)
+    }
+}
@@ -0,0 +1,365 @@
+//
+// day10.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use std::hash::{ BuildHasher, Hasher };
+use Solution;
+
+
+/// Knot-tying engine: a circular list of numbers, initially in increasing
+/// order, in which each length reverses a section starting at the current
+/// position. The list itself is the sparse hash.
+///
+/// Sections are reversed as contiguous slices. Those which wrap around the
+/// end of the list are first copied to a scratch buffer, reversed there, and
+/// copied back, which avoids calculating a modulo for each swapped element.
+#[derive(Clone, Debug)]
+pub struct Knot
+{
+    list: Vec<u32>,
+    scratch: Vec<u32>,
+    pos: usize,
+    skip: usize,
+}
+
+impl Knot
+{
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "Knot list size must be positive");
+        assert!(size <= u32::MAX as usize);
+        Knot {
+            list: (0 .. size as u32).collect(),
+            scratch: Vec::with_capacity(size),
+            pos: 0,
+            skip: 0,
+        }
+    }
+
+    #[inline]
+    pub fn size(&self) -> usize {
+        self.list.len()
+    }
+
+    /// Ties a knot of a given length, which must not be bigger than the
+    /// size of the list.
+    pub fn tie(&mut self, n: usize) {
+        assert!(n <= self.list.len(), "Length {} is bigger than the list size", n);
+
+        // 1. Reverse the order of elements pos..n
+        let len = self.list.len();
+        let end = self.pos + n;
+        if end <= len {
+            self.list[self.pos .. end].reverse();
+        } else {
+            let wrapped = end - len;
+            self.scratch.clear();
+            self.scratch.extend_from_slice(&self.list[self.pos ..]);
+            self.scratch.extend_from_slice(&self.list[.. wrapped]);
+            self.scratch.reverse();
+            let (head, tail) = self.scratch.split_at(len - self.pos);
+            self.list[self.pos ..].copy_from_slice(head);
+            self.list[.. wrapped].copy_from_slice(tail);
+        }
+
+        // 2. Increase position by n+skip
+        self.pos = (end + self.skip) % len;
+
+        // 3. Increase skip by one.
+        self.skip += 1;
+    }
+
+    /// Ties one knot for each length, followed by the suffix lengths, and
+    /// repeats the whole sequence a number of rounds.
+    pub fn rounds(&mut self, lengths: &[usize], suffix: &[usize], rounds: usize) {
+        for _ in 0 .. rounds {
+            for &n in lengths.iter().chain(suffix) {
+                self.tie(n);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn sparse_hash(&self) -> &[u32] {
+        &self.list
+    }
+
+    /// Reduces the sparse hash by combining groups of consecutive numbers
+    /// with XOR. The last group is shorter if the size of the list is not
+    /// a multiple of the group size.
+    pub fn dense_hash(&self, group_size: usize) -> Vec<u32> {
+        self.list.chunks(group_size)
+            .map(|group| group.iter().fold(0, |acc, x| acc ^ x))
+            .collect()
+    }
+}
+
+
+/// Straightforward implementation of `Knot`, which swaps elements one by
+/// one wrapping around the end of the list. It is kept to check the results
+/// of `Knot`, and to benchmark against it.
+#[derive(Clone, Debug)]
+pub struct ReferenceKnot
+{
+    list: Vec<u32>,
+    pos: usize,
+    skip: usize,
+}
+
+impl ReferenceKnot
+{
+    pub fn new(size: usize) -> Self {
+        assert!(size > 0, "Knot list size must be positive");
+        assert!(size <= u32::MAX as usize);
+        ReferenceKnot { list: (0 .. size as u32).collect(), pos: 0, skip: 0 }
+    }
+
+    pub fn tie(&mut self, n: usize) {
+        assert!(n <= self.list.len(), "Length {} is bigger than the list size", n);
+
+        // 1. Reverse the order of elements pos..n (the loop is written
+        //    so that a length of zero does not underflow).
+        let len = self.list.len();
+        let mut i = self.pos;
+        let mut j = self.pos + n;
+        while i + 1 < j {
+            j -= 1;
+            self.list.swap(i % len, j % len);
+            i += 1;
+        }
+
+        // 2. Increase position by n+skip
+        self.pos = (self.pos + n + self.skip) % len;
+
+        // 3. Increase skip by one.
+        self.skip += 1;
+    }
+
+    pub fn rounds(&mut self, lengths: &[usize], suffix: &[usize], rounds: usize) {
+        for _ in 0 .. rounds {
+            for &n in lengths.iter().chain(suffix) {
+                self.tie(n);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn sparse_hash(&self) -> &[u32] {
+        &self.list
+    }
+}
+
+
+/// Knot hash digest. Input is accumulated using `update()`, and the dense
+/// hash is calculated by `finalize()`.
+#[derive(Clone, Debug, Default)]
+pub struct KnotHash
+{
+    input: Vec<u8>,
+}
+
+impl KnotHash
+{
+    pub const SIZE: usize = 256;
+    pub const ROUNDS: usize = 64;
+    pub const SUFFIX: [usize; 5] = [17, 31, 73, 47, 23];
+    pub const XORITEMS: usize = 16;
+
+    pub fn new() -> Self {
+        KnotHash { input: Vec::new() }
+    }
+
+    /// Calculates the digest of a complete input.
+    pub fn digest(input: &[u8]) -> [u8; 16] {
+        let mut kh = Self::new();
+        kh.update(input);
+        kh.finalize()
+    }
+
+    #[inline]
+    pub fn update(&mut self, input: &[u8]) {
+        self.input.extend_from_slice(input);
+    }
+
+    pub fn finalize(self) -> [u8; 16] {
+        let lengths: Vec<usize> = self.input.iter().map(|&b| b as usize).collect();
+        let mut knot = Knot::new(Self::SIZE);
+        knot.rounds(&lengths, &Self::SUFFIX, Self::ROUNDS);
+
+        let mut dense = [0u8; 16];
+        for (byte, xor) in dense.iter_mut().zip(knot.dense_hash(Self::XORITEMS)) {
+            *byte = xor as u8;
+        }
+        dense
+    }
+}
+
+
+/// Adapter to use `KnotHash` as a `Hasher`. Written data is buffered, and
+/// the first eight bytes of the dense hash are used as the result. This is
+/// way slower than the default hasher, and not meant for serious use.
+#[derive(Clone, Debug, Default)]
+pub struct KnotHasher
+{
+    hash: KnotHash,
+}
+
+impl Hasher for KnotHasher
+{
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hash.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        let digest = self.hash.clone().finalize();
+        digest[.. 8].iter().fold(0, |acc, &b| (acc << 8) | u64::from(b))
+    }
+}
+
+
+/// Creates `KnotHasher` instances, e.g. for `HashMap::with_hasher()`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildKnotHasher;
+
+impl BuildHasher for BuildKnotHasher
+{
+    type Hasher = KnotHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> KnotHasher {
+        KnotHasher::default()
+    }
+}
+
+
+/// Encodes a digest as a string of lowercase hexadecimal digits.
+pub fn encode_hex(digest: &[u8]) -> String
+{
+    digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+
+/// Encodes a digest as a string of ones and zeros, most significant bit first.
+pub fn encode_binary(digest: &[u8]) -> String
+{
+    digest.iter().map(|byte| format!("{:08b}", byte)).collect()
+}
+
+
+/// Ties one round of knots on a list of a given size, using the lengths
+/// from a comma-separated input, and multiplies the first two numbers.
+fn knot_product(size: usize, input: &str) -> Result<u32, Error>
+{
+    let mut knot = Knot::new(size);
+    for length in input.split(',') {
+        let length: usize = length.trim().parse()?;
+        ensure!(length <= knot.size(),
+                "Length {} is bigger than the list size", length);
+        knot.tie(length);
+    }
+    let list = knot.sparse_hash();
+    ensure!(list.len() >= 2, "The list must contain at least two numbers");
+    Ok(list[0] * list[1])
+}
+
+
+pub struct Day10;
+
+impl Solution for Day10
+{
+    type Input = String;
+    type Part1 = u32;
+    type Part2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(input.trim().to_string())
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32, Error>
+    {
+        knot_product(256, input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, Error>
+    {
+        Ok(encode_hex(&KnotHash::digest(input.as_bytes())))
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn worked_example() {
+        let mut knot = Knot::new(5);
+        knot.rounds(&[3, 4, 1, 5], &[], 1);
+        assert_eq!(knot.sparse_hash(), &[3, 4, 2, 1, 0]);
+        let mut reference = ReferenceKnot::new(5);
+        reference.rounds(&[3, 4, 1, 5], &[], 1);
+        assert_eq!(reference.sparse_hash(), &[3, 4, 2, 1, 0]);
+        assert_eq!(knot_product(5, "3,4,1,5").unwrap(), 12);
+    }
+
+    #[test]
+    fn same_as_reference() {
+        for size in 1 .. 20 {
+            let lengths: Vec<usize> = (0 ..= size).chain((0 ..= size).rev()).collect();
+            let mut knot = Knot::new(size);
+            let mut reference = ReferenceKnot::new(size);
+            knot.rounds(&lengths, &[size, 1], 7);
+            reference.rounds(&lengths, &[size, 1], 7);
+            assert_eq!(knot.sparse_hash(), reference.sparse_hash());
+        }
+    }
+
+    #[test]
+    fn digest() {
+        assert_eq!(encode_hex(&KnotHash::digest(b"")), "a2582a3a0e66e6e86e3812dcb672a272");
+        assert_eq!(encode_hex(&KnotHash::digest(b"AoC 2017")), "33efeb34ea91902bb2f59c9920caa6cd");
+        assert_eq!(encode_hex(&KnotHash::digest(b"1,2,3")), "3efbe78a8d82f29979031a4aa0b16a9d");
+        assert_eq!(encode_hex(&KnotHash::digest(b"1,2,4")), "63960835bcdc130f0b66d7ff4f6a5a8e");
+    }
+
+    #[test]
+    fn hasher_finish() {
+        let mut hasher = KnotHasher::default();
+        hasher.write(b"AoC ");
+        hasher.write(b"2017");
+        assert_eq!(hasher.finish(), 0x33efeb34ea91902b);
+        // Finishing does not reset the hasher.
+        assert_eq!(hasher.finish(), 0x33efeb34ea91902b);
+        hasher.write(b"!");
+        assert_ne!(hasher.finish(), 0x33efeb34ea91902b);
+    }
+
+    #[test]
+    fn hasher_in_hashmap() {
+        let mut map: HashMap<String, u32, _> = HashMap::with_hasher(BuildKnotHasher);
+        for (i, word) in ["pbga", "xhth", "ebii", "havc", "ktlj"].iter().enumerate() {
+            map.insert(word.to_string(), i as u32);
+        }
+        *map.entry("xhth".to_string()).or_insert(0) += 10;
+
+        assert_eq!(map.len(), 5);
+        assert_eq!(map.get("pbga"), Some(&0));
+        assert_eq!(map.get("xhth"), Some(&11));
+        assert_eq!(map.remove("ktlj"), Some(4));
+        assert_eq!(map.get("ktlj"), None);
+        assert_eq!(map.get("tknk"), None);
+    }
+
+    #[test]
+    fn hasher_is_deterministic() {
+        let build = BuildKnotHasher;
+        assert_eq!(build.hash_one("padx"), build.hash_one("padx"));
+        assert_ne!(build.hash_one("padx"), build.hash_one("fwft"));
+    }
+}
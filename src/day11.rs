@@ -0,0 +1,154 @@
+//
+// day11.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+// Awesome resource on hex grid tiling algorithms:
+//    https://www.redblobgames.com/grids/hexagons/
+
+use failure::Error;
+use std::str::FromStr;
+use { parse_lines, Solution };
+
+
+#[derive(Debug, Copy, Clone)]
+pub enum Dir {
+    N(u32),
+    NE(u32),
+    SE(u32),
+    S(u32),
+    SW(u32),
+    NW(u32),
+}
+
+impl FromStr for Dir
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        Ok(match s {
+            "n"  => Dir::N(1),
+            "ne" => Dir::NE(1),
+            "se" => Dir::SE(1),
+            "s"  => Dir::S(1),
+            "sw" => Dir::SW(1),
+            "nw" => Dir::NW(1),
+            _    => bail!("invalid direction: {}", s),
+        })
+    }
+}
+
+
+/// Sequence of comma-separated steps.
+struct Steps(Vec<Dir>);
+
+impl FromStr for Steps
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        let mut steps = Vec::new();
+        for step in s.split(',') {
+            steps.push(step.trim().parse()?);
+        }
+        Ok(Steps(steps))
+    }
+}
+
+
+mod coord {
+    use super::Dir;
+
+    #[derive(Default, Debug, Copy, Clone)]
+    pub struct Axis { q: isize, r: isize }
+
+    impl Axis {
+        #[inline]
+        pub fn new(q: isize, r: isize) -> Self { Axis { q, r } }
+
+        #[inline]
+        fn to_cube(self) -> Cube { Cube::new(self.q, -self.q - self.r, self.r) }
+
+        pub fn add(&self, d: Dir) -> Self {
+            match d {
+                Dir::N(s)  => Axis::new(self.q, self.r - s as isize),
+                Dir::NE(s) => Axis::new(self.q + s as isize, self.r - s as isize),
+                Dir::SE(s) => Axis::new(self.q + s as isize, self.r),
+                Dir::S(s)  => Axis::new(self.q, self.r + s as isize),
+                Dir::SW(s) => Axis::new(self.q - s as isize, self.r + s as isize),
+                Dir::NW(s) => Axis::new(self.q - s as isize, self.r),
+            }
+        }
+
+        pub fn distance_to(&self, other: &Axis) -> usize {
+            self.to_cube().distance_to(&other.to_cube())
+        }
+    }
+
+    struct Cube { x: isize, y: isize, z: isize }
+
+    impl Cube {
+        #[inline]
+        pub fn new(x: isize, y: isize, z: isize) -> Self {
+            assert_eq!(0, x + y + z);
+            Cube { x, y, z }
+        }
+
+        #[inline]
+        pub fn distance_to(&self, other: &Cube) -> usize {
+            ((self.x - other.x).unsigned_abs() +
+             (self.y - other.y).unsigned_abs() +
+             (self.z - other.z).unsigned_abs()) / 2
+        }
+    }
+}
+
+
+/// Walks the path, and returns the distance from the origin to the final
+/// position, and the maximum distance reached along the way.
+fn walk(path: &[Dir]) -> (usize, usize)
+{
+    let origin = coord::Axis::default();
+    let mut pos = origin;
+    let mut max_steps = 0;
+    for &d in path {
+        pos = pos.add(d);
+        let steps = pos.distance_to(&origin);
+        if steps > max_steps {
+            max_steps = steps;
+        }
+    }
+    (pos.distance_to(&origin), max_steps)
+}
+
+
+pub struct Day11;
+
+impl Solution for Day11
+{
+    type Input = Vec<Dir>;
+    type Part1 = usize;
+    type Part2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        // Steps may be split among multiple lines.
+        Ok(parse_lines::<Steps, _>(input.as_bytes())?
+           .into_iter()
+           .flat_map(|steps| steps.0)
+           .collect())
+    }
+
+    fn part1(path: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(walk(path).0)
+    }
+
+    fn part2(path: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(walk(path).1)
+    }
+}
@@ -0,0 +1,94 @@
+//
+// day12.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use std::collections::HashMap;
+use std::str::FromStr;
+use unionfind::UnionFind;
+use { parse_lines, Solution };
+
+
+#[derive(Debug, Clone)]
+pub struct Pipe
+{
+    from: u32,
+    to: Vec<u32>,
+}
+
+impl FromStr for Pipe
+{
+    type Err = ::failure::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err>
+    {
+        if let Some(pos) = s.find("<->") {
+            let (from_str, to_str) = s.split_at(pos);
+            let mut to = Vec::new();
+            for num_str in to_str[3..].split(',').map(str::trim) {
+                to.push(num_str.parse()?);
+            }
+            Ok(Pipe { from: from_str.trim().parse()?, to })
+        } else {
+            bail!("Invalid pipe specification: '{}'", s)
+        }
+    }
+}
+
+
+/// Groups the pipes which are connected to each other. Pipe identifiers
+/// are mapped to consecutive indices, which are returned along with the
+/// sets of connected pipes.
+fn connect_pipes(pipes: &HashMap<u32, Pipe>) -> Result<(HashMap<u32, usize>, UnionFind), Error>
+{
+    let indices: HashMap<u32, usize> = pipes.keys()
+        .enumerate()
+        .map(|(index, &pipe_id)| (pipe_id, index))
+        .collect();
+
+    let mut groups = UnionFind::new(indices.len());
+    for pipe in pipes.values() {
+        for pipe_id in &pipe.to {
+            let other = if let Some(&index) = indices.get(pipe_id) { index } else {
+                bail!("No pipe with id={} found", pipe_id);
+            };
+            groups.union(indices[&pipe.from], other);
+        }
+    }
+    Ok((indices, groups))
+}
+
+
+pub struct Day12;
+
+impl Solution for Day12
+{
+    type Input = HashMap<u32, Pipe>;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        Ok(parse_lines::<Pipe, _>(input.as_bytes())?
+           .into_iter()
+           .map(|p| (p.from, p))
+           .collect())
+    }
+
+    fn part1(pipes: &Self::Input) -> Result<usize, Error>
+    {
+        let (indices, mut groups) = connect_pipes(pipes)?;
+        match indices.get(&0) {
+            Some(&index) => Ok(groups.size(index)),
+            None => bail!("No pipe with id=0 found"),
+        }
+    }
+
+    fn part2(pipes: &Self::Input) -> Result<u32, Error>
+    {
+        let (_, groups) = connect_pipes(pipes)?;
+        Ok(groups.count() as u32)
+    }
+}
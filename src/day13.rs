@@ -0,0 +1,247 @@
+//
+// day13.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use grid::Grid;
+use std::fmt;
+use Solution;
+
+
+#[derive(Debug, Clone)]
+struct Layer
+{
+    len: u32,
+    pos: u32,
+    fwd: bool,  // Going down (forward) or up (backward).
+}
+
+impl Layer
+{
+    fn new(range: u32) -> Self {
+        Self { len: range, pos: 0, fwd: true }
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.pos = 0;
+        self.fwd = true;
+    }
+
+    #[inline]
+    fn range(&self) -> u32 {
+        self.len
+    }
+
+    #[inline]
+    fn tick(&mut self) {
+        if self.len > 1 {
+            if self.fwd {
+                if self.pos == self.len - 1 {
+                    self.fwd = false;
+                    self.pos -= 1;
+                } else {
+                    self.pos += 1;
+                }
+            } else if self.pos == 0 {
+                self.fwd = true;
+                self.pos += 1;
+            } else {
+                self.pos -= 1;
+            }
+        }
+    }
+
+    fn scanner_at_top(&self) -> bool {
+        self.len > 0 && self.pos == 0
+    }
+}
+
+
+pub struct Firewall
+{
+    layers: Vec<Layer>,
+    pos: usize,
+    initial: bool,
+    collision: Option<u32>,
+}
+
+impl Clone for Firewall {
+    fn clone(&self) -> Self {
+        Self {
+            layers: self.layers.iter().map(Layer::clone).collect(),
+            pos: self.pos,
+            initial: self.initial,
+            collision: self.collision,
+        }
+    }
+}
+
+impl fmt::Debug for Firewall
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max_range = self.layers.iter().map(Layer::range).max().unwrap_or(0);
+        let cells = Grid::from_fn(self.layers.len(), max_range as usize, |i, row| {
+            let layer = &self.layers[i];
+            let row = row as u32;
+            if row == 0 && self.pos == i {
+                let item = if layer.range() == 0 { "." }
+                      else if layer.pos == row { "S" }
+                      else { " " };
+                if self.initial {
+                    format!("[{}]", item)
+                } else {
+                    format!("({})", item)
+                }
+            } else if layer.range() <= row {
+                "...".to_string()
+            } else {
+                format!("[{}]", if layer.pos == row { "S" } else { " " })
+            }
+        });
+
+        for i in 0 .. self.layers.len() {
+            write!(f, " {}  ", i)?;
+        }
+        writeln!(f)?;
+        f.write_str(&cells.render(" ", |cell| cell.clone()))
+    }
+}
+
+impl Firewall
+{
+    fn new() -> Self {
+        Self {
+            layers: Vec::new(),
+            pos: 0,
+            initial: true,
+            collision: None,
+        }
+    }
+
+    fn set(&mut self, depth: u32, layer: Layer) {
+        let depth = depth as usize;
+        while self.layers.len() <= depth {
+            self.layers.push(Layer::new(0));  // Fill with empty layers.
+        }
+        assert!(depth < self.layers.len());
+        self.layers[depth] = layer;
+    }
+
+    #[inline]
+    fn reset(&mut self) {
+        self.layers.iter_mut().for_each(Layer::reset);
+        self.reset_packet();
+    }
+
+    #[inline]
+    fn reset_packet(&mut self) {
+        self.collision = None;
+        self.initial = true;
+        self.pos = 0;
+    }
+
+    #[inline]
+    fn finished(&self) -> bool {
+        self.pos >= self.layers.len()
+    }
+
+    #[inline]
+    fn collided(&self) -> bool {
+        !self.finished() && self.layers[self.pos].scanner_at_top()
+    }
+
+    #[inline]
+    fn collision_severity(&self) -> Option<u32> {
+        if self.collided() {
+            Some(self.pos as u32 * self.layers[self.pos].range())
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn tick(&mut self) {
+        if !self.finished() {
+            if self.initial {
+                self.initial = false;
+            } else {
+                self.pos += 1;
+            }
+            self.collision = self.collision_severity();
+            self.layers.iter_mut().for_each(Layer::tick);
+        }
+    }
+
+    fn trip_severity(&mut self) -> Option<u32> {
+        let mut total_severity = 0;
+        let mut caught = false;
+        while !self.finished() {
+            self.tick();
+            if let Some(severity) = self.collision {
+                total_severity += severity;
+                caught = true;
+            }
+        }
+        if caught {
+            Some(total_severity)
+        } else {
+            None
+        }
+    }
+}
+
+
+pub struct Day13;
+
+impl Solution for Day13
+{
+    type Input = Firewall;
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        let mut fw = Firewall::new();
+        for line in input.lines() {
+            if let Some(pos) = line.find(':') {
+                let (left, right) = line.split_at(pos);
+                let depth = left.trim().parse()?;
+                let range = right[1..].trim().parse()?;
+                fw.set(depth, Layer::new(range));
+            }
+        }
+        Ok(fw)
+    }
+
+    fn part1(fw: &Self::Input) -> Result<u32, Error>
+    {
+        let mut fw = fw.clone();
+        fw.reset();
+        Ok(fw.trip_severity().unwrap_or(0))
+    }
+
+    fn part2(fw: &Self::Input) -> Result<u32, Error>
+    {
+        ensure!(fw.layers.iter().all(|layer| layer.range() != 1),
+                "A layer with range 1 catches the packet with any delay");
+
+        let mut fw = fw.clone();
+        fw.reset();
+        let mut delay = 0;
+        loop {
+            // Check what the severity of the trip would be now. We use a clone
+            // so we can continue using "fw" to calculate states after the delay.
+            if fw.clone().trip_severity().is_none() {
+                return Ok(delay);
+            }
+
+            // Add one tick of delay, reset packet to initial position.
+            fw.tick();
+            fw.reset_packet();
+            delay += 1;
+        }
+    }
+}
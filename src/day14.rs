@@ -0,0 +1,641 @@
+//
+// day14.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use day10::KnotHash;
+use failure::Error;
+use grid::{ Grid, Neighbours };
+use num::Num;
+use std::fmt;
+use std::io::{ self, Read, Write };
+use std::ops::Index;
+use std::thread;
+use unionfind::UnionFind;
+use { name_input_error, Solution };
+
+
+pub fn make_row_hash(key: &str, row: usize) -> [u8; 16] {
+    KnotHash::digest(format!("{}-{}", key, row).as_bytes())
+}
+
+
+/// Dimensions of the disk grid. Each row is made of one or more hashes,
+/// each one providing `HASH_BITS` columns.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct DiskSize
+{
+    pub rows: usize,
+    pub hashes_per_row: usize,
+}
+
+impl DiskSize
+{
+    pub const HASH_BITS: usize = 128;
+
+    #[inline]
+    pub fn columns(&self) -> usize {
+        self.hashes_per_row * Self::HASH_BITS
+    }
+}
+
+impl Default for DiskSize
+{
+    fn default() -> Self {
+        DiskSize { rows: 128, hashes_per_row: 1 }
+    }
+}
+
+
+/// Position of a square in the disk grid. Fields are named so the row
+/// and the column cannot be swapped by mistake.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct Point
+{
+    pub row: usize,
+    pub col: usize,
+}
+
+impl fmt::Display for Point
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "row {}, col {}", self.row, self.col)
+    }
+}
+
+
+/// Sets the bits in a row of the bitmap, most significant bit first. The
+/// hashes for a row are keyed as `key-row` when there is one hash per row
+/// (as in the puzzle), and as `key-row-index` otherwise.
+fn fill_row(bits: &mut [bool], key: &str, row: usize)
+{
+    let hashes_per_row = bits.len() / DiskSize::HASH_BITS;
+    for (index, bits) in bits.chunks_mut(DiskSize::HASH_BITS).enumerate() {
+        let hash = if hashes_per_row == 1 {
+            make_row_hash(key, row)
+        } else {
+            KnotHash::digest(format!("{}-{}-{}", key, row, index).as_bytes())
+        };
+        for (i, byte) in hash.iter().enumerate() {
+            for bit in 0 .. 8 {
+                bits[i * 8 + bit] = byte & (0x80 >> bit) != 0;
+            }
+        }
+    }
+}
+
+
+pub struct Bitmap
+{
+    bits: Grid<bool>,
+}
+
+
+impl Bitmap
+{
+    fn new(size: DiskSize) -> Self {
+        Self { bits: Grid::new(size.columns(), size.rows, false) }
+    }
+
+    /// Builds a bitmap from rows of squares, where `true` marks the used
+    /// ones. All the rows must have the same length.
+    pub fn from_rows<R: AsRef<[bool]>>(rows: &[R]) -> Self {
+        let width = rows.first().map_or(0, |row| row.as_ref().len());
+        assert!(rows.iter().all(|row| row.as_ref().len() == width), "Rows must have the same length");
+        Self { bits: Grid::from_fn(width, rows.len(), |col, row| rows[row].as_ref()[col]) }
+    }
+
+    /// Builds the bitmap for a key. When `parallel` is set, the row hashes
+    /// are calculated by as many threads as available cores, each one filling
+    /// a band of rows.
+    pub fn from_key(key: &str, size: DiskSize, parallel: bool) -> Self {
+        let mut bmap = Self::new(size);
+        let mut rows: Vec<_> = bmap.bits.rows_mut().enumerate().collect();
+        if parallel {
+            let threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+            let band = rows.len().div_ceil(threads).max(1);
+            thread::scope(|scope| {
+                for band in rows.chunks_mut(band) {
+                    scope.spawn(move || {
+                        for (row, bits) in band {
+                            fill_row(bits, key, *row);
+                        }
+                    });
+                }
+            });
+        } else {
+            for (row, bits) in rows {
+                fill_row(bits, key, row);
+            }
+        }
+        bmap
+    }
+
+    #[inline] pub fn width(&self) -> usize { self.bits.width() }
+    #[inline] pub fn height(&self) -> usize { self.bits.height() }
+
+    pub fn count_used(&self) -> usize {
+        self.bits.cells().filter(|&(_, &used)| used).count()
+    }
+
+    /// Writes the bitmap as a binary PBM image, with used squares in black.
+    pub fn write_pbm<W: Write>(&self, mut output: W) -> io::Result<()> {
+        writeln!(output, "P4\n{} {}", self.bits.width(), self.bits.height())?;
+        for row in self.bits.rows() {
+            let packed: Vec<u8> = row.chunks(8).map(|bits| {
+                bits.iter().enumerate().fold(0, |byte, (bit, &used)| {
+                    if used { byte | (0x80 >> bit) } else { byte }
+                })
+            }).collect();
+            output.write_all(&packed)?;
+        }
+        Ok(())
+    }
+
+    /// Iterates over the positions of all the squares, row by row.
+    pub fn points(&self) -> impl Iterator<Item=Point> {
+        let width = self.bits.width();
+        (0 .. self.bits.height()).flat_map(move |row| (0 .. width).map(move |col| Point { row, col }))
+    }
+
+    #[inline]
+    fn neighbours(&self, p: Point, connectivity: Connectivity) -> impl Iterator<Item=Point> {
+        let neighbours: Neighbours = match connectivity {
+            Connectivity::Four => self.bits.neighbours4(p.col, p.row),
+            Connectivity::Eight => self.bits.neighbours8(p.col, p.row),
+        };
+        neighbours.map(|(col, row)| Point { row, col })
+    }
+}
+
+impl Index<Point> for Bitmap
+{
+    type Output = bool;
+
+    #[inline]
+    fn index(&self, p: Point) -> &bool {
+        &self.bits[(p.col, p.row)]
+    }
+}
+
+
+/// Which squares are considered adjacent when labeling regions: either only
+/// those which share a side, or also those which touch diagonally.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Connectivity
+{
+    Four,
+    Eight,
+}
+
+
+pub struct BitmapLabels<T: Num + Clone>
+{
+    bits: Grid<T>,
+}
+
+
+impl<T: Num + Clone> BitmapLabels<T>
+{
+    fn new(width: usize, height: usize) -> Self {
+        Self { bits: Grid::new(width, height, T::zero()) }
+    }
+
+    #[inline]
+    fn set(&mut self, p: Point, value: T) {
+        self.bits[(p.col, p.row)] = value;
+    }
+}
+
+impl<T: Num + Clone> Index<Point> for BitmapLabels<T>
+{
+    type Output = T;
+
+    #[inline]
+    fn index(&self, p: Point) -> &T {
+        &self.bits[(p.col, p.row)]
+    }
+}
+
+
+impl BitmapLabels<u32>
+{
+    /// Writes the labels as a binary PPM image, with a different colour
+    /// for each label, and unused squares in white.
+    pub fn write_ppm<W: Write>(&self, mut output: W) -> io::Result<()> {
+        writeln!(output, "P6\n{} {}\n255", self.bits.width(), self.bits.height())?;
+        for row in self.bits.rows() {
+            let pixels: Vec<u8> = row.iter().flat_map(|&label| label_colour(label)).collect();
+            output.write_all(&pixels)?;
+        }
+        Ok(())
+    }
+}
+
+
+/// Picks a colour for a region label. Hues are spread using the golden
+/// angle, so labels assigned one after the other get quite different colours.
+fn label_colour(label: u32) -> [u8; 3]
+{
+    if label == 0 {
+        return [255, 255, 255];
+    }
+
+    let (s, v) = (0.7, 0.9);
+    let h = (f64::from(label) * 137.507_764) % 360.0 / 60.0;
+    let c = v * s;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = v - c;
+    [((r + m) * 255.0) as u8, ((g + m) * 255.0) as u8, ((b + m) * 255.0) as u8]
+}
+
+
+/// Average position of the squares in a region.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Centroid
+{
+    pub row: f64,
+    pub col: f64,
+}
+
+
+/// Statistics for a region of used squares. The bounding box goes from
+/// `min` to `max`, including both corners.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Region
+{
+    pub label: u32,
+    pub size: usize,
+    pub min: Point,
+    pub max: Point,
+    pub centroid: Centroid,
+}
+
+
+/// Result of labeling a bitmap: the label of each square, and the
+/// statistics for each region, ordered by label.
+pub struct Regions
+{
+    pub labels: BitmapLabels<u32>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions
+{
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns the region with most squares. On ties, the one with the
+    /// lowest label is returned.
+    pub fn largest(&self) -> Option<&Region> {
+        self.regions.iter().fold(None, |best: Option<&Region>, region| match best {
+            Some(best) if best.size >= region.size => Some(best),
+            _ => Some(region),
+        })
+    }
+
+    /// Returns the region with fewer squares. On ties, the one with the
+    /// lowest label is returned.
+    pub fn smallest(&self) -> Option<&Region> {
+        self.regions.iter().fold(None, |best: Option<&Region>, region| match best {
+            Some(best) if best.size <= region.size => Some(best),
+            _ => Some(region),
+        })
+    }
+}
+
+
+/// Labels all the connected areas of used squares. Labels start at one,
+/// and are assigned in the order in which regions are first found when
+/// scanning the bitmap row by row.
+pub fn label_regions(used: &Bitmap, connectivity: Connectivity) -> Regions
+{
+    let width = used.width();
+    let index = |p: Point| p.row * width + p.col;
+
+    // Merge each used square with its used neighbours. Afterwards, the
+    // squares of each region have the same representative in the set.
+    let mut sets = UnionFind::new(width * used.height());
+    for p in used.points().filter(|&p| used[p]) {
+        for n in used.neighbours(p, connectivity) {
+            if used[n] {
+                sets.union(index(p), index(n));
+            }
+        }
+    }
+
+    // Assign a new label to the representative of each region the first
+    // time one of its squares is found, then copy it to the square.
+    let mut labels = BitmapLabels::new(width, used.height());
+    let mut regions: Vec<Region> = Vec::new();
+    let mut sums = Vec::new();
+    for p in used.points().filter(|&p| used[p]) {
+        let root = sets.find(index(p));
+        let root = Point { row: root / width, col: root % width };
+        if labels[root] == 0 {
+            labels.set(root, regions.len() as u32 + 1);
+            regions.push(Region {
+                label: regions.len() as u32 + 1,
+                size: 0,
+                min: p,
+                max: p,
+                centroid: Centroid { row: 0.0, col: 0.0 },
+            });
+            sums.push((0, 0));
+        }
+        let label = labels[root];
+        labels.set(p, label);
+
+        let i = label as usize - 1;
+        let region = &mut regions[i];
+        region.size += 1;
+        region.min = Point { row: region.min.row.min(p.row), col: region.min.col.min(p.col) };
+        region.max = Point { row: region.max.row.max(p.row), col: region.max.col.max(p.col) };
+        sums[i] = (sums[i].0 + p.row, sums[i].1 + p.col);
+    }
+
+    for (region, &(row, col)) in regions.iter_mut().zip(&sums) {
+        let size = region.size as f64;
+        region.centroid = Centroid { row: row as f64 / size, col: col as f64 / size };
+    }
+
+    Regions { labels, regions }
+}
+
+
+pub fn parse_key(input: &str) -> Result<String, Error>
+{
+    let key = input.trim();
+    ensure!(!key.is_empty(), "Input does not contain a key");
+    Ok(key.to_string())
+}
+
+
+/// Command line options shared by the day 14 programs, which choose the
+/// size of the disk and whether the row hashes are calculated in parallel.
+#[derive(Debug, Default, Copy, Clone)]
+pub struct DiskOptions
+{
+    pub size: DiskSize,
+    pub parallel: bool,
+}
+
+impl DiskOptions
+{
+    pub const USAGE: &'static str = "[--parallel] [--rows <n>] [--hashes-per-row <n>]";
+
+    /// Handles an argument, taking the value of the option from the rest
+    /// of the arguments if needed. Returns whether it was recognized.
+    pub fn parse_arg<I: Iterator<Item=String>>(&mut self, arg: &str, args: &mut I) -> Result<bool, Error> {
+        match arg {
+            "--parallel" => self.parallel = true,
+            "--rows" => self.size.rows = parse_count(arg, args.next())?,
+            "--hashes-per-row" => self.size.hashes_per_row = parse_count(arg, args.next())?,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Reads the key from the standard input, and builds its bitmap.
+    pub fn read_bitmap(&self) -> Result<Bitmap, Error> {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        let key = parse_key(&input).map_err(|e| name_input_error(e, "<stdin>"))?;
+        Ok(Bitmap::from_key(&key, self.size, self.parallel))
+    }
+}
+
+
+fn parse_count(option: &str, value: Option<String>) -> Result<usize, Error>
+{
+    match value.map(|value| value.parse()) {
+        Some(Ok(count)) if count > 0 => Ok(count),
+        _ => bail!("Option '{}' requires a positive number", option),
+    }
+}
+
+
+pub struct Day14;
+
+impl Solution for Day14
+{
+    type Input = String;
+    type Part1 = usize;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        parse_key(input)
+    }
+
+    fn part1(key: &Self::Input) -> Result<usize, Error>
+    {
+        Ok(Bitmap::from_key(key, DiskSize::default(), false).count_used())
+    }
+
+    fn part2(key: &Self::Input) -> Result<u32, Error>
+    {
+        Ok(label_regions(&Bitmap::from_key(key, DiskSize::default(), false), Connectivity::Four).count() as u32)
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    const KEY: &str = "flqrgnkx";
+
+    fn size(rows: usize, hashes_per_row: usize) -> DiskSize {
+        DiskSize { rows, hashes_per_row }
+    }
+
+    #[test]
+    fn pbm() {
+        // Bits are packed most significant first, so each row of a single
+        // hash is written as the bytes of the hash.
+        let bitmap = Bitmap::from_key(KEY, size(2, 1), false);
+        let mut pbm = Vec::new();
+        bitmap.write_pbm(&mut pbm).unwrap();
+        let (header, pixels) = pbm.split_at(9);
+        assert_eq!(header, b"P4\n128 2\n");
+        assert_eq!(&pixels[.. 16], &make_row_hash(KEY, 0));
+        assert_eq!(&pixels[16 ..], &make_row_hash(KEY, 1));
+        assert_eq!(pixels[0], 0b1101_0100);  // ##.#.#..
+    }
+
+    #[test]
+    fn ppm() {
+        // The first row starts with ##.#.#.., which has three regions.
+        let regions = label_regions(&Bitmap::from_key(KEY, size(1, 1), false), Connectivity::Four);
+        let mut ppm = Vec::new();
+        regions.labels.write_ppm(&mut ppm).unwrap();
+        let (header, pixels) = ppm.split_at(13);
+        assert_eq!(header, b"P6\n128 1\n255\n");
+        assert_eq!(pixels.len(), 128 * 3);
+        let pixel = |col: usize| &pixels[col * 3 .. col * 3 + 3];
+        assert_eq!(pixel(0), label_colour(1));
+        assert_eq!(pixel(1), label_colour(1));
+        assert_eq!(pixel(2), [255, 255, 255]);
+        assert_eq!(pixel(3), label_colour(2));
+        assert_eq!(pixel(5), label_colour(3));
+    }
+
+    #[test]
+    fn colours() {
+        assert_eq!(label_colour(0), [255, 255, 255]);
+        for label in 1 .. 1000 {
+            assert_ne!(label_colour(label), label_colour(label + 1), "label {}", label);
+            assert_ne!(label_colour(label), [255, 255, 255], "label {}", label);
+        }
+    }
+
+    fn bitmap(rows: &[&str]) -> Bitmap {
+        let rows: Vec<Vec<bool>> = rows.iter().map(|row| row.chars().map(|c| c == '#').collect()).collect();
+        Bitmap::from_rows(&rows)
+    }
+
+    #[test]
+    fn example() {
+        let bitmap = Bitmap::from_key(KEY, DiskSize::default(), false);
+        assert_eq!(bitmap.count_used(), 8108);
+        assert_eq!(label_regions(&bitmap, Connectivity::Four).count(), 1242);
+    }
+
+    #[test]
+    fn diagonal() {
+        let bitmap = bitmap(&["#.#.",
+                              ".#..",
+                              "#.#.",
+                              "...#"]);
+        assert_eq!(label_regions(&bitmap, Connectivity::Four).count(), 6);
+        let regions = label_regions(&bitmap, Connectivity::Eight);
+        assert_eq!(regions.count(), 1);
+        assert_eq!(regions.regions[0].size, 6);
+    }
+
+    #[test]
+    fn region_stats() {
+        let bitmap = bitmap(&["##..",
+                              "#..#",
+                              "...#",
+                              ".#.."]);
+        let regions = label_regions(&bitmap, Connectivity::Four);
+        assert_eq!(regions.regions, [
+            Region {
+                label: 1,
+                size: 3,
+                min: Point { row: 0, col: 0 },
+                max: Point { row: 1, col: 1 },
+                centroid: Centroid { row: 1.0 / 3.0, col: 1.0 / 3.0 },
+            },
+            Region {
+                label: 2,
+                size: 2,
+                min: Point { row: 1, col: 3 },
+                max: Point { row: 2, col: 3 },
+                centroid: Centroid { row: 1.5, col: 3.0 },
+            },
+            Region {
+                label: 3,
+                size: 1,
+                min: Point { row: 3, col: 1 },
+                max: Point { row: 3, col: 1 },
+                centroid: Centroid { row: 3.0, col: 1.0 },
+            },
+        ]);
+        assert_eq!(regions.labels[Point { row: 1, col: 0 }], 1);
+        assert_eq!(regions.labels[Point { row: 2, col: 3 }], 2);
+        assert_eq!(regions.labels[Point { row: 2, col: 2 }], 0);
+        assert_eq!(regions.largest().map(|r| r.label), Some(1));
+        assert_eq!(regions.smallest().map(|r| r.label), Some(3));
+    }
+
+    #[test]
+    fn region_ties() {
+        let regions = label_regions(&bitmap(&["#.##.#", "#....#"]), Connectivity::Four);
+        assert_eq!(regions.regions.iter().map(|r| r.size).collect::<Vec<_>>(), [2, 2, 2]);
+        assert_eq!(regions.largest().map(|r| r.label), Some(1));
+        assert_eq!(regions.smallest().map(|r| r.label), Some(1));
+
+        let regions = label_regions(&bitmap(&["#..##", "#..#."]), Connectivity::Four);
+        assert_eq!(regions.largest().map(|r| r.label), Some(2));
+        assert_eq!(regions.smallest().map(|r| r.label), Some(1));
+
+        let regions = label_regions(&bitmap(&["....", "...."]), Connectivity::Eight);
+        assert_eq!(regions.count(), 0);
+        assert!(regions.largest().is_none());
+        assert!(regions.smallest().is_none());
+    }
+
+    /// Bit of a hash, most significant first.
+    fn hash_bit(hash: &[u8; 16], bit: usize) -> bool {
+        hash[bit / 8] & (0x80 >> (bit % 8)) != 0
+    }
+
+    #[test]
+    fn hashes_per_row() {
+        let bitmap = Bitmap::from_key(KEY, size(2, 3), false);
+        assert_eq!((bitmap.height(), bitmap.width()), (2, 3 * 128));
+        for row in 0 .. 2 {
+            for index in 0 .. 3 {
+                let hash = KnotHash::digest(format!("{}-{}-{}", KEY, row, index).as_bytes());
+                for bit in 0 .. 128 {
+                    assert_eq!(bitmap[Point { row, col: index * 128 + bit }], hash_bit(&hash, bit),
+                               "row {}, hash {}, bit {}", row, index, bit);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn options() {
+        let mut options = DiskOptions::default();
+        let mut args = vec!["5", "2", "0", "x"].into_iter().map(String::from);
+        assert!(options.parse_arg("--rows", &mut args).unwrap());
+        assert!(options.parse_arg("--hashes-per-row", &mut args).unwrap());
+        assert!(options.parse_arg("--rows", &mut args).is_err());
+        assert!(options.parse_arg("--rows", &mut args).is_err());
+        assert!(options.parse_arg("--rows", &mut args).is_err());
+        assert!(!options.parse_arg("--pbm", &mut args).unwrap());
+        assert!(!options.parallel);
+        assert!(options.parse_arg("--parallel", &mut args).unwrap());
+        assert!(options.parallel);
+        assert_eq!(options.size, size(5, 2));
+    }
+
+    #[test]
+    fn non_square() {
+        let hashed = Bitmap::from_key(KEY, size(2, 1), false);
+        assert_eq!((hashed.height(), hashed.width()), (2, 128));
+        for row in 0 .. 2 {
+            let hash = make_row_hash(KEY, row);
+            for col in 0 .. 128 {
+                assert_eq!(hashed[Point { row, col }], hash_bit(&hash, col), "row {}, col {}", row, col);
+            }
+        }
+
+        let bitmap = bitmap(&["#....##",
+                              "##..#.."]);
+        let regions = label_regions(&bitmap, Connectivity::Four);
+        assert_eq!(regions.count(), 3);
+        assert_eq!(regions.labels[Point { row: 1, col: 1 }], 1);
+        assert_eq!(regions.labels[Point { row: 0, col: 6 }], 2);
+        assert_eq!(regions.labels[Point { row: 1, col: 4 }], 3);
+        assert_eq!(regions.labels[Point { row: 1, col: 6 }], 0);
+        assert_eq!(regions.regions[1].max, Point { row: 0, col: 6 });
+        assert_eq!(label_regions(&bitmap, Connectivity::Eight).count(), 2);
+    }
+}
@@ -0,0 +1,86 @@
+//
+// day15.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::Error;
+use Solution;
+
+
+struct Generator
+{
+    last: u64,
+    factor: u64,
+    multiplier: u64,
+}
+
+
+impl Generator
+{
+    const DIVIDER: u64 = 2147483647;
+    const FACTORA: u64 = 16807;
+    const FACTORB: u64 = 48271;
+
+    fn new(start: u64, factor: u64, multiplier: u64) -> Self {
+        Self { last: start, factor, multiplier }
+    }
+
+    fn next(&mut self) -> u64 {
+        loop {
+            self.last = (self.last * self.factor) % Self::DIVIDER;
+            if self.last.is_multiple_of(self.multiplier) {
+                break;
+            }
+        }
+        self.last
+    }
+}
+
+
+fn count_matches(mut a: Generator, mut b: Generator, npairs: usize) -> u32
+{
+    let mut matches = 0;
+    for _ in 0 .. npairs {
+        if a.next() & 0xFFFF == b.next() & 0xFFFF {
+            matches += 1;
+        }
+    }
+    matches
+}
+
+
+pub struct Day15;
+
+impl Solution for Day15
+{
+    type Input = (u64, u64);
+    type Part1 = u32;
+    type Part2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, Error>
+    {
+        // Accept both a plain "<a> <b>" pair and the puzzle text, which
+        // has the numbers at the end of each "Generator X starts with" line.
+        let values: Vec<u64> = input.split_whitespace()
+            .filter_map(|s| s.parse().ok())
+            .collect();
+        ensure!(values.len() == 2,
+                "Expected two generator start values, got {}", values.len());
+        Ok((values[0], values[1]))
+    }
+
+    fn part1(&(a, b): &Self::Input) -> Result<u32, Error>
+    {
+        Ok(count_matches(Generator::new(a, Generator::FACTORA, 1),
+                         Generator::new(b, Generator::FACTORB, 1),
+                         40 * 1000 * 1000))
+    }
+
+    fn part2(&(a, b): &Self::Input) -> Result<u32, Error>
+    {
+        Ok(count_matches(Generator::new(a, Generator::FACTORA, 4),
+                         Generator::new(b, Generator::FACTORB, 8),
+                         5 * 1000 * 1000))
+    }
+}
@@ -0,0 +1,495 @@
+//
+// grid.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use std::collections::HashMap;
+use std::fmt;
+use std::iter::{ Skip, StepBy };
+use std::ops::{ Index, IndexMut };
+use std::slice;
+
+
+/// Bounded, rectangular 2D array. Cells are addressed using `(x, y)`
+/// coordinates, where `x` is the column and `y` is the row, both starting
+/// at zero on the top-left corner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T>
+{
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+
+impl<T: Clone> Grid<T>
+{
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Grid { cells: vec![value; width * height], width, height }
+    }
+}
+
+
+impl<T> Grid<T>
+{
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+        where F: FnMut(usize, usize) -> T
+    {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0 .. height {
+            for x in 0 .. width {
+                cells.push(f(x, y));
+            }
+        }
+        Grid { cells, width, height }
+    }
+
+    #[inline] pub fn width(&self) -> usize { self.width }
+    #[inline] pub fn height(&self) -> usize { self.height }
+
+    #[inline]
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    #[inline]
+    fn offset(&self, x: usize, y: usize) -> Option<usize> {
+        if self.contains(x, y) {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn wrapping_offset(&self, x: isize, y: isize) -> usize {
+        assert!(!self.cells.is_empty(), "Wrapping access to an empty grid");
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        y * self.width + x
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.offset(x, y).map(|i| &self.cells[i])
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        match self.offset(x, y) {
+            Some(i) => Some(&mut self.cells[i]),
+            None => None,
+        }
+    }
+
+    /// Stores a value in a cell, returning the previous value, or `None`
+    /// (and dropping the value) if the coordinates are out of bounds.
+    pub fn set(&mut self, x: usize, y: usize, value: T) -> Option<T> {
+        self.get_mut(x, y).map(|cell| ::std::mem::replace(cell, value))
+    }
+
+    /// Access a cell, with coordinates wrapping around the edges.
+    #[inline]
+    pub fn wrapping(&self, x: isize, y: isize) -> &T {
+        &self.cells[self.wrapping_offset(x, y)]
+    }
+
+    #[inline]
+    pub fn wrapping_mut(&mut self, x: isize, y: isize) -> &mut T {
+        let i = self.wrapping_offset(x, y);
+        &mut self.cells[i]
+    }
+
+    /// Iterates over the coordinates of the up, left, right, and down
+    /// neighbours of a cell which are inside the grid.
+    pub fn neighbours4(&self, x: usize, y: usize) -> Neighbours {
+        Neighbours::new(self, x, y, &NEIGHBOURS4)
+    }
+
+    /// Iterates over the coordinates of the neighbours of a cell which
+    /// are inside the grid, including the diagonal ones.
+    pub fn neighbours8(&self, x: usize, y: usize) -> Neighbours {
+        Neighbours::new(self, x, y, &NEIGHBOURS8)
+    }
+
+    #[inline]
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "Row out of bounds: {} >= {}", y, self.height);
+        &self.cells[y * self.width .. (y + 1) * self.width]
+    }
+
+    #[inline]
+    pub fn rows(&self) -> slice::Chunks<'_, T> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    #[inline]
+    pub fn rows_mut(&mut self) -> slice::ChunksMut<'_, T> {
+        self.cells.chunks_mut(self.width.max(1))
+    }
+
+    #[inline]
+    pub fn column(&self, x: usize) -> StepBy<Skip<slice::Iter<'_, T>>> {
+        assert!(x < self.width, "Column out of bounds: {} >= {}", x, self.width);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item=StepBy<Skip<slice::Iter<'_, T>>>> {
+        (0 .. self.width).map(move |x| self.column(x))
+    }
+
+    /// Iterates over all the cells, along with their coordinates.
+    pub fn cells(&self) -> impl Iterator<Item=((usize, usize), &T)> {
+        let width = self.width;
+        self.cells.iter().enumerate().map(move |(i, cell)| ((i % width, i / width), cell))
+    }
+
+    /// Renders the grid as text, one line per row, using a function to
+    /// convert each cell into text, and placing a separator between cells.
+    pub fn render<F, D>(&self, separator: &str, mut f: F) -> String
+        where F: FnMut(&T) -> D, D: fmt::Display
+    {
+        let mut text = String::new();
+        for row in self.rows() {
+            for (x, cell) in row.iter().enumerate() {
+                if x > 0 {
+                    text.push_str(separator);
+                }
+                text.push_str(&f(cell).to_string());
+            }
+            text.push('\n');
+        }
+        text
+    }
+}
+
+
+impl<T> Index<(usize, usize)> for Grid<T>
+{
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        match self.offset(x, y) {
+            Some(i) => &self.cells[i],
+            None => panic!("Index out of bounds: ({}, {}) in {}x{} grid",
+                           x, y, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T>
+{
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        match self.offset(x, y) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("Index out of bounds: ({}, {}) in {}x{} grid",
+                           x, y, self.width, self.height),
+        }
+    }
+}
+
+
+impl<T: fmt::Display> fmt::Display for Grid<T>
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.render(" ", |cell| cell.to_string()))
+    }
+}
+
+
+const NEIGHBOURS4: [(isize, isize); 4] = [
+             (0, -1),
+    (-1, 0),          (1, 0),
+             (0,  1),
+];
+
+const NEIGHBOURS8: [(isize, isize); 8] = [
+    (-1, -1), (0, -1), (1, -1),
+    (-1,  0),          (1,  0),
+    (-1,  1), (0,  1), (1,  1),
+];
+
+
+pub struct Neighbours
+{
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    steps: &'static [(isize, isize)],
+}
+
+impl Neighbours
+{
+    fn new<T>(grid: &Grid<T>, x: usize, y: usize, steps: &'static [(isize, isize)]) -> Self {
+        Neighbours { x, y, width: grid.width, height: grid.height, steps }
+    }
+}
+
+impl Iterator for Neighbours
+{
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((&(dx, dy), rest)) = self.steps.split_first() {
+            self.steps = rest;
+            let (nx, ny) = (self.x as isize + dx, self.y as isize + dy);
+            if nx >= 0 && ny >= 0 && (nx as usize) < self.width && (ny as usize) < self.height {
+                return Some((nx as usize, ny as usize));
+            }
+        }
+        None
+    }
+}
+
+
+/// Unbounded 2D grid using signed coordinates, which only stores the cells
+/// which have been written to. Reading any other cell returns the default
+/// value given when creating the grid.
+#[derive(Clone, Debug)]
+pub struct SparseGrid<T>
+{
+    cells: HashMap<(isize, isize), T>,
+    default: T,
+    min: (isize, isize),
+    max: (isize, isize),
+}
+
+
+impl<T> SparseGrid<T>
+{
+    pub fn new(default: T) -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            default,
+            min: (0, 0),
+            max: (0, 0),
+        }
+    }
+
+    /// Number of cells which have been written to.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    #[inline]
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        self.cells.contains_key(&(x, y))
+    }
+
+    #[inline]
+    pub fn get(&self, x: isize, y: isize) -> &T {
+        self.cells.get(&(x, y)).unwrap_or(&self.default)
+    }
+
+    pub fn set(&mut self, x: isize, y: isize, value: T) -> Option<T> {
+        self.grow(x, y);
+        self.cells.insert((x, y), value)
+    }
+
+    /// Smallest and largest coordinates of the cells which have been
+    /// written to, or `None` for an empty grid.
+    pub fn bounds(&self) -> Option<((isize, isize), (isize, isize))> {
+        if self.cells.is_empty() {
+            None
+        } else {
+            Some((self.min, self.max))
+        }
+    }
+
+    #[inline]
+    fn grow(&mut self, x: isize, y: isize) {
+        if self.cells.is_empty() {
+            self.min = (x, y);
+            self.max = (x, y);
+        } else {
+            self.min = (self.min.0.min(x), self.min.1.min(y));
+            self.max = (self.max.0.max(x), self.max.1.max(y));
+        }
+    }
+
+    /// Iterates over the cells which have been written to, in no
+    /// particular order.
+    pub fn cells(&self) -> impl Iterator<Item=((isize, isize), &T)> {
+        self.cells.iter().map(|(&pos, cell)| (pos, cell))
+    }
+
+    pub fn neighbours4(&self, x: isize, y: isize) -> impl Iterator<Item=(isize, isize)> {
+        NEIGHBOURS4.iter().map(move |&(dx, dy)| (x + dx, y + dy))
+    }
+
+    pub fn neighbours8(&self, x: isize, y: isize) -> impl Iterator<Item=(isize, isize)> {
+        NEIGHBOURS8.iter().map(move |&(dx, dy)| (x + dx, y + dy))
+    }
+
+    /// Renders the area of the grid covered by the cells written to, in the
+    /// same way as `Grid::render`.
+    pub fn render<F, D>(&self, separator: &str, mut f: F) -> String
+        where F: FnMut(&T) -> D, D: fmt::Display
+    {
+        let mut text = String::new();
+        if let Some(((xmin, ymin), (xmax, ymax))) = self.bounds() {
+            for y in ymin ..= ymax {
+                for x in xmin ..= xmax {
+                    if x > xmin {
+                        text.push_str(separator);
+                    }
+                    text.push_str(&f(self.get(x, y)).to_string());
+                }
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+
+impl<T: Clone> SparseGrid<T>
+{
+    /// Returns a mutable reference to a cell, which is created with the
+    /// default value if it was not written to before.
+    pub fn get_mut(&mut self, x: isize, y: isize) -> &mut T {
+        self.grow(x, y);
+        let default = &self.default;
+        self.cells.entry((x, y)).or_insert_with(|| default.clone())
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    /// Grid with three columns and two rows, numbered in reading order.
+    fn numbered() -> Grid<u32> {
+        Grid::from_fn(3, 2, |x, y| (y * 3 + x) as u32)
+    }
+
+    fn sorted(neighbours: Neighbours) -> Vec<(usize, usize)> {
+        let mut neighbours: Vec<_> = neighbours.collect();
+        neighbours.sort();
+        neighbours
+    }
+
+    #[test]
+    fn get_set() {
+        let mut grid = numbered();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.get(2, 0), Some(&2));
+        assert_eq!(grid.get(0, 1), Some(&3));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid.get(2, 1), Some(&5));
+        assert_eq!(grid.set(2, 1, 50), Some(5));
+        assert_eq!(grid[(2, 1)], 50);
+        assert_eq!(grid.set(1, 2, 60), None);
+        assert_eq!(grid.set(3, 1, 60), None);
+        assert!(grid.cells().all(|(_, &cell)| cell != 60));
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut grid = numbered();
+        assert_eq!(*grid.wrapping(0, 0), 0);
+        assert_eq!(*grid.wrapping(-1, 0), 2);
+        assert_eq!(*grid.wrapping(0, -1), 3);
+        assert_eq!(*grid.wrapping(-4, -3), 5);
+        assert_eq!(*grid.wrapping(3, 2), 0);
+        assert_eq!(*grid.wrapping(7, 5), 4);
+        *grid.wrapping_mut(-2, 3) = 40;
+        assert_eq!(grid[(1, 1)], 40);
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = numbered();
+        assert_eq!(sorted(grid.neighbours4(0, 0)), [(0, 1), (1, 0)]);
+        assert_eq!(sorted(grid.neighbours4(1, 0)), [(0, 0), (1, 1), (2, 0)]);
+        assert_eq!(sorted(grid.neighbours4(2, 1)), [(1, 1), (2, 0)]);
+        assert_eq!(sorted(grid.neighbours8(0, 0)), [(0, 1), (1, 0), (1, 1)]);
+        assert_eq!(sorted(grid.neighbours8(1, 1)), [(0, 0), (0, 1), (1, 0), (2, 0), (2, 1)]);
+        assert_eq!(sorted(grid.neighbours8(2, 1)), [(1, 0), (1, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn rows_columns() {
+        let grid = numbered();
+        let rows: Vec<_> = grid.rows().collect();
+        assert_eq!(rows, [&[0, 1, 2], &[3, 4, 5]]);
+        assert_eq!(grid.row(1), &[3, 4, 5]);
+        assert_eq!(grid.column(1).cloned().collect::<Vec<_>>(), [1, 4]);
+        let columns: Vec<Vec<_>> = grid.columns().map(|c| c.cloned().collect()).collect();
+        assert_eq!(columns, [[0, 3], [1, 4], [2, 5]]);
+        let cells: Vec<_> = grid.cells().map(|(pos, &cell)| (pos, cell)).collect();
+        assert_eq!(cells[..4], [((0, 0), 0), ((1, 0), 1), ((2, 0), 2), ((0, 1), 3)]);
+    }
+
+    #[test]
+    fn render() {
+        let grid = numbered();
+        assert_eq!(grid.render(",", |cell| cell * 10), "0,10,20\n30,40,50\n");
+        assert_eq!(grid.to_string(), "0 1 2\n3 4 5\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "Index out of bounds: (1, 2) in 3x2 grid")]
+    fn index_out_of_bounds() {
+        let _ = numbered()[(1, 2)];
+    }
+
+    #[test]
+    fn sparse_default() {
+        let mut grid = SparseGrid::new('.');
+        assert!(grid.is_empty());
+        assert_eq!(grid.bounds(), None);
+        assert_eq!(grid.render("", |&c| c), "");
+        assert_eq!(*grid.get(5, -7), '.');
+        assert_eq!(grid.set(1, 1, '#'), None);
+        assert_eq!(grid.set(1, 1, '@'), Some('#'));
+        assert_eq!(*grid.get(1, 1), '@');
+        assert_eq!(*grid.get(0, 0), '.');
+        assert!(!grid.contains(0, 0));
+        assert_eq!(grid.len(), 1);
+    }
+
+    #[test]
+    fn sparse_bounds() {
+        let mut grid = SparseGrid::new(0);
+        grid.set(2, 3, 1);
+        assert_eq!(grid.bounds(), Some(((2, 3), (2, 3))));
+        grid.set(-1, 4, 2);
+        grid.set(0, -2, 3);
+        assert_eq!(grid.bounds(), Some(((-1, -2), (2, 4))));
+    }
+
+    #[test]
+    fn sparse_get_mut() {
+        let mut grid = SparseGrid::new(10);
+        *grid.get_mut(-3, 1) += 5;
+        assert!(grid.contains(-3, 1));
+        assert_eq!(*grid.get(-3, 1), 15);
+        assert_eq!(grid.bounds(), Some(((-3, 1), (-3, 1))));
+        *grid.get_mut(1, -1) += 1;
+        assert_eq!(grid.bounds(), Some(((-3, -1), (1, 1))));
+        assert_eq!(grid.len(), 2);
+    }
+
+    #[test]
+    fn sparse_render() {
+        let mut grid = SparseGrid::new('.');
+        grid.set(-2, -1, 'a');
+        grid.set(0, 0, 'b');
+        grid.set(-1, 1, 'c');
+        assert_eq!(grid.render(" ", |&c| c), "a . .\n. . b\n. c .\n");
+    }
+}
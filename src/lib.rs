@@ -4,47 +4,300 @@
 // Distributed under terms of the MIT license.
 //
 
+extern crate ego_tree;
+#[macro_use] extern crate failure;
+extern crate num;
+extern crate permutohedron;
+
+use failure::{ Error, Fail };
+use std::fmt;
 use std::io::{ self, BufRead, Read };
 use std::str::FromStr;
 
 
-#[inline]
-fn is_ascii_digit(byte: &u8) -> bool
+/// Calls a generic function instantiated with the `Solution` for a given
+/// day number, e.g. `with_solution!(day, solve_part(part, input))`.
+macro_rules! with_solution {
+    ($day:expr, $func:ident ( $($arg:expr),* )) => {
+        match $day {
+             1 => $func::<::day01::Day01>($($arg),*),
+             2 => $func::<::day02::Day02>($($arg),*),
+             3 => $func::<::day03::Day03>($($arg),*),
+             4 => $func::<::day04::Day04>($($arg),*),
+             5 => $func::<::day05::Day05>($($arg),*),
+             6 => $func::<::day06::Day06>($($arg),*),
+             7 => $func::<::day07::Day07>($($arg),*),
+             8 => $func::<::day08::Day08>($($arg),*),
+             9 => $func::<::day09::Day09>($($arg),*),
+            10 => $func::<::day10::Day10>($($arg),*),
+            11 => $func::<::day11::Day11>($($arg),*),
+            12 => $func::<::day12::Day12>($($arg),*),
+            13 => $func::<::day13::Day13>($($arg),*),
+            14 => $func::<::day14::Day14>($($arg),*),
+            15 => $func::<::day15::Day15>($($arg),*),
+            day => bail!("No solution for day {}", day),
+        }
+    }
+}
+
+
+pub mod bench;
+pub mod cycle;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod grid;
+pub mod unionfind;
+pub mod verify;
+
+
+/// Error in the puzzle input, with the position of the offending token.
+/// Lines and columns are numbered starting at one. As with `LineError`,
+/// the name of the input may be set later on using `name_input_error`.
+#[derive(Debug)]
+pub struct InputError
+{
+    pub name: Option<String>,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl InputError
+{
+    pub fn new<S: Into<String>>(line: usize, column: usize, message: S) -> Self {
+        InputError { name: None, line, column, message: message.into() }
+    }
+}
+
+impl fmt::Display for InputError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(f, "{}:{}:{}: {}", name, self.line, self.column, self.message),
+            None => write!(f, "line {}, column {}: {}", self.line, self.column, self.message),
+        }
+    }
+}
+
+impl Fail for InputError {}
+
+
+/// Error produced when a line of input cannot be parsed, wrapping the
+/// error returned by the parser. The name of the input is optional, and
+/// may be set later on using `name_input_error`.
+#[derive(Debug)]
+pub struct LineError
+{
+    pub name: Option<String>,
+    pub line: usize,
+    pub error: Error,
+}
+
+impl fmt::Display for LineError
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.name {
+            Some(ref name) => write!(f, "{}:{}: {}", name, self.line, self.error),
+            None => write!(f, "line {}: {}", self.line, self.error),
+        }
+    }
+}
+
+impl Fail for LineError
+{
+    fn cause(&self) -> Option<&dyn Fail> {
+        Some(self.error.as_fail())
+    }
+}
+
+
+/// Sets the input name in a `LineError` or an `InputError`, which is useful
+/// when the input was read from a file. Other kinds of errors are returned
+/// unchanged.
+pub fn name_input_error(e: Error, name: &str) -> Error
 {
-    *byte >= 0x30 && *byte <= 0x39
+    let e = match e.downcast::<LineError>() {
+        Ok(mut e) => {
+            e.name = Some(name.to_string());
+            return e.into();
+        },
+        Err(e) => e,
+    };
+    match e.downcast::<InputError>() {
+        Ok(mut e) => {
+            e.name = Some(name.to_string());
+            e.into()
+        },
+        Err(e) => e,
+    }
 }
 
 
-fn ascii_digit(byte: u8) -> u32
+#[inline]
+fn ascii_digit(byte: u8) -> Option<u32>
 {
-    if is_ascii_digit(&byte) {
-        byte as u32 - 0x30
+    if byte.is_ascii_digit() {
+        Some(u32::from(byte - b'0'))
     } else {
-        panic!("Byte 0x{:02X} is not a number", byte)
+        None
+    }
+}
+
+
+pub struct Digits<R>
+{
+    bytes: io::Bytes<R>,
+    line: usize,
+    column: usize,
+    done: bool,
+}
+
+impl<R: Read> Iterator for Digits<R>
+{
+    type Item = Result<u32, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let byte = match self.bytes.next() {
+                None => break,
+                Some(Ok(byte)) => byte,
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e.into()));
+                },
+            };
+
+            if byte == b'\n' {
+                self.line += 1;
+                self.column = 1;
+                continue;
+            }
+
+            let column = self.column;
+            self.column += 1;
+            if byte.is_ascii_whitespace() {
+                continue;
+            }
+
+            return Some(match ascii_digit(byte) {
+                Some(digit) => Ok(digit),
+                None => {
+                    self.done = true;
+                    let message = if byte.is_ascii_graphic() {
+                        format!("'{}' is not a digit", byte as char)
+                    } else {
+                        format!("byte 0x{:02X} is not a digit", byte)
+                    };
+                    Err(InputError::new(self.line, column, message).into())
+                },
+            });
+        }
+        None
     }
 }
 
 
-pub fn iter_digits<R: Read>(iter: io::Bytes<R>) -> impl Iterator<Item=u32>
+/// Iterates over the decimal digits read from a byte stream. Whitespace
+/// is skipped, and any other byte which is not a digit produces an error,
+/// after which the iteration stops.
+pub fn iter_digits<R: Read>(iter: io::Bytes<R>) -> Digits<R>
 {
-    iter.map(|item| item.unwrap())
-        .take_while(is_ascii_digit)
-        .map(ascii_digit)
+    Digits { bytes: iter, line: 1, column: 1, done: false }
+}
+
+
+/// Splits a line into whitespace-separated tokens, returning each one along
+/// with the byte offset at which it starts.
+pub fn tokens_with_offsets(line: &str) -> Vec<(usize, &str)>
+{
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in line.char_indices() {
+        match (c.is_whitespace(), start) {
+            (true, Some(s)) => {
+                tokens.push((s, &line[s .. i]));
+                start = None;
+            },
+            (false, None) => start = Some(i),
+            _ => (),
+        }
+    }
+    if let Some(s) = start {
+        tokens.push((s, &line[s ..]));
+    }
+    tokens
 }
 
 
-pub fn rows_of_digits<BR: BufRead>(iter: BR) -> impl Iterator<Item=Vec<i32>>
+/// Iterates over rows of whitespace-separated integers. Tokens which
+/// cannot be parsed produce an error indicating their line and column.
+pub fn rows_of_digits<BR: BufRead>(iter: BR) -> impl Iterator<Item=Result<Vec<i32>, Error>>
 {
     iter.lines()
-        .filter_map(io::Result::ok)
-        .map(|line| {
-            line.split_whitespace()
-                .map(|s| i32::from_str(s).unwrap())
-                .collect()
+        .enumerate()
+        .map(|(lineno, line)| {
+            let line = line?;
+            let mut row = Vec::new();
+            for (offset, token) in tokens_with_offsets(&line) {
+                match i32::from_str(token) {
+                    Ok(value) => row.push(value),
+                    Err(e) => return Err(InputError::new(lineno + 1, offset + 1,
+                                                         format!("'{}': {}", token, e)).into()),
+                }
+            }
+            Ok(row)
         })
 }
 
 
+/// Parses each line of the input into a value. Empty lines and comment
+/// lines (those starting with a `#` character) are skipped. On failure,
+/// the error is wrapped into a `LineError` which indicates the line number.
+pub fn parse_lines<T, R>(reader: R) -> Result<Vec<T>, Error>
+    where T: FromStr, T::Err: Into<Error>, R: BufRead
+{
+    Ok(parse_numbered_lines(reader)?.into_iter().map(|(_, item)| item).collect())
+}
+
+
+/// Same as `parse_lines`, but each value is returned along with the number
+/// of the line it was parsed from, starting at one.
+pub fn parse_numbered_lines<T, R>(reader: R) -> Result<Vec<(usize, T)>, Error>
+    where T: FromStr, T::Err: Into<Error>, R: BufRead
+{
+    let mut items = Vec::new();
+    for (lineno, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.parse() {
+            Ok(item) => items.push((lineno + 1, item)),
+            Err(e) => return Err(LineError {
+                name: None,
+                line: lineno + 1,
+                error: Into::<Error>::into(e),
+            }.into()),
+        }
+    }
+    Ok(items)
+}
+
+
 #[derive(Debug)]
 pub struct VecPermutations<T> {
    v: Vec<T>,
@@ -98,122 +351,142 @@ impl<T> Permutations for Vec<T>
 }
 
 
-pub mod day03 {
-    pub fn grid_size_for_cell(cellindex: u32) -> u32
-    {
-        let mut size = 1;
-        while cellindex > size * size {
-            size += 2;
-        }
-        return size;
-    }
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Part
+{
+    A,
+    B,
 }
 
-
-pub mod day10
+impl FromStr for Part
 {
-    use std::fmt;
+    type Err = Error;
 
-    pub struct KnotHash
+    fn from_str(s: &str) -> Result<Self, Self::Err>
     {
-        list: [u8; 256],
-        pos: usize,
-        skip: usize,
+        Ok(match s {
+            "a" | "A" | "1" => Part::A,
+            "b" | "B" | "2" => Part::B,
+            _ => bail!("Invalid puzzle part '{}'", s),
+        })
     }
+}
 
-    impl KnotHash
-    {
-        const ROUNDS: usize = 64;
-        const XORITEMS: usize = 16;
-        const XORGROUPS: usize = 256 / Self::XORITEMS;
-
-        pub fn new() -> Self {
-            let mut kh = KnotHash { list: [0u8; 256], pos: 0, skip: 0 };
-            for i in 0 .. kh.list.len() {
-                kh.list[i] = i as u8;
-            }
-            kh
-        }
+impl fmt::Display for Part
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self { Part::A => "a", Part::B => "b" })
+    }
+}
 
-        fn apply(&mut self, n: u8) {
-            // 1. Reverse the order of elements pos..n
-            let len = self.list.len();
-            let mut i = self.pos;
-            let mut j = self.pos + (n as usize) - 1;
-            while i < j {
-                self.list.swap(i % len, j % len);
-                i += 1;
-                j -= 1;
-            }
 
-            // 2. Increase position by n+skip
-            self.pos = (self.pos + (n as usize) + self.skip) % self.list.len();
+/// Solution to the puzzle of a day. The input is first parsed into a
+/// value of type `Input`, which is then used to solve each part.
+pub trait Solution
+{
+    type Input;
+    type Part1: fmt::Display;
+    type Part2: fmt::Display;
 
-            // 3. Increase skip by one.
-            self.skip += 1;
-        }
+    fn parse(input: &str) -> Result<Self::Input, Error>;
+    fn part1(input: &Self::Input) -> Result<Self::Part1, Error>;
+    fn part2(input: &Self::Input) -> Result<Self::Part2, Error>;
+}
 
-        #[inline]
-        pub fn rounds(&mut self, input: &[u8]) {
-            for _ in 0..Self::ROUNDS {
-                for &b in input {
-                    self.apply(b);
-                }
-                // Apply the standard tail lengths.
-                for &b in &[17, 31, 73, 47, 23] {
-                    self.apply(b);
-                }
-            }
+
+/// Parses the input and solves one part of the puzzle, returning the
+/// answer formatted as a string.
+pub fn solve_part<S: Solution>(part: Part, input: &str) -> Result<String, Error>
+{
+    let input = S::parse(input)?;
+    Ok(match part {
+        Part::A => S::part1(&input)?.to_string(),
+        Part::B => S::part2(&input)?.to_string(),
+    })
+}
+
+
+/// Reads the puzzle input from the standard input, and prints the answers
+/// for the given parts.
+pub fn run_stdin<S: Solution>(parts: &[Part]) -> Result<(), Error>
+{
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let input = S::parse(&input).map_err(|e| name_input_error(e, "<stdin>"))?;
+    for part in parts {
+        match *part {
+            Part::A => println!("{}", S::part1(&input)?),
+            Part::B => println!("{}", S::part2(&input)?),
         }
     }
+    Ok(())
+}
 
-    impl fmt::LowerHex for KnotHash
-    {
-        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-            for group in 0 .. Self::XORGROUPS {
-                let startpos = group * Self::XORITEMS;
-                let mut xor = 0;
-                for x in &self.list[startpos .. startpos + Self::XORITEMS] {
-                    xor ^= x;
-                }
-                write!(f, "{:02x}", xor)?;
-            }
-            Ok(())
-        }
+
+/// Entry point used by the programs for each day: runs the solution using
+/// the standard input, and exits with an error message on failure.
+pub fn main<S: Solution>(parts: &[Part])
+{
+    if let Err(e) = run_stdin::<S>(parts) {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
     }
 }
 
 
-pub mod day14
+pub const DAYS: u32 = 15;
+
+
+/// Solves one part of the puzzle for a given day, and returns the answer
+/// formatted as a string.
+pub fn solve(day: u32, part: Part, input: &str) -> Result<String, Error>
+{
+    with_solution!(day, solve_part(part, input))
+}
+
+
+#[cfg(test)]
+mod tests
 {
-    use super::day10::KnotHash;
+    use super::*;
 
-    pub fn make_row_hash(key: &str, row: u16) -> String {
-        let input = format!("{}-{}", key, row);
-        let mut kh = KnotHash::new();
-        kh.rounds(input.as_bytes());
-        format!("{:x}", kh)
+    #[test]
+    fn digits() {
+        let digits: Vec<_> = iter_digits(" 12\n\t3 4\n".as_bytes().bytes()).map(Result::unwrap).collect();
+        assert_eq!(digits, [1, 2, 3, 4]);
     }
 
-    pub fn hex_char_to_u8(c: char) -> u8 {
-        match c {
-            '0' => 0x0,
-            '1' => 0x1,
-            '2' => 0x2,
-            '3' => 0x3,
-            '4' => 0x4,
-            '5' => 0x5,
-            '6' => 0x6,
-            '7' => 0x7,
-            '8' => 0x8,
-            '9' => 0x9,
-            'a' | 'A' => 0xA,
-            'b' | 'B' => 0xB,
-            'c' | 'C' => 0xC,
-            'd' | 'D' => 0xD,
-            'e' | 'E' => 0xE,
-            'f' | 'F' => 0xF,
-            _ => panic!("Non-hex character: '{}'", c),
-        }
+    #[test]
+    fn digits_error_position() {
+        let mut digits = iter_digits("12\n 3x4\n".as_bytes().bytes());
+        assert_eq!(digits.next().unwrap().unwrap(), 1);
+        assert_eq!(digits.next().unwrap().unwrap(), 2);
+        assert_eq!(digits.next().unwrap().unwrap(), 3);
+        let e = digits.next().unwrap().unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 3: 'x' is not a digit");
+        assert!(digits.next().is_none());
+        assert_eq!(name_input_error(e, "input").to_string(), "input:2:3: 'x' is not a digit");
+    }
+
+    #[test]
+    fn rows_error_position() {
+        let mut rows = rows_of_digits("1 2\n3\t 4  x5\n".as_bytes());
+        assert_eq!(rows.next().unwrap().unwrap(), [1, 2]);
+        let e = rows.next().unwrap().unwrap_err();
+        assert_eq!(e.to_string(), "line 2, column 7: 'x5': invalid digit found in string");
+    }
+
+    #[test]
+    fn lines_skipped() {
+        let input = "# Comment\n1\n\n   \n  # Indented\n2\n3\n";
+        assert_eq!(parse_lines::<u32, _>(input.as_bytes()).unwrap(), [1, 2, 3]);
+        assert_eq!(parse_numbered_lines::<u32, _>(input.as_bytes()).unwrap(), [(2, 1), (6, 2), (7, 3)]);
+    }
+
+    #[test]
+    fn lines_error_position() {
+        let e = parse_lines::<u32, _>("1\n\n# Comment\nx\n".as_bytes()).unwrap_err();
+        assert_eq!(e.to_string(), "line 4: invalid digit found in string");
+        assert_eq!(name_input_error(e, "input").to_string(), "input:4: invalid digit found in string");
     }
 }
@@ -0,0 +1,174 @@
+//
+// unionfind.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use std::collections::HashMap;
+
+
+/// Disjoint-set forest, with path compression and union by rank. Elements
+/// are identified by indices, starting at zero. Initially each element
+/// is in a component of its own.
+#[derive(Clone, Debug)]
+pub struct UnionFind
+{
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+
+impl UnionFind
+{
+    pub fn new(n: usize) -> Self {
+        UnionFind {
+            parent: (0 .. n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    /// Number of elements.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Number of components.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Adds a new element in a component of its own, and returns its index.
+    pub fn push(&mut self) -> usize {
+        let index = self.parent.len();
+        self.parent.push(index);
+        self.rank.push(0);
+        self.size.push(1);
+        self.count += 1;
+        index
+    }
+
+    /// Finds the representative element of the component which contains
+    /// an element. All the elements in the path to the representative are
+    /// updated to point directly to it.
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    /// Merges the components which contain two elements. Returns `false`
+    /// if the elements were already in the same component.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+        let (root, child) = if self.rank[a] < self.rank[b] { (b, a) } else { (a, b) };
+        if self.rank[a] == self.rank[b] {
+            self.rank[root] += 1;
+        }
+        self.parent[child] = root;
+        self.size[root] += self.size[child];
+        self.count -= 1;
+        true
+    }
+
+    #[inline]
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    /// Number of elements in the component which contains an element.
+    #[inline]
+    pub fn size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    /// Returns the sizes of all the components, indexed by their
+    /// representative element.
+    pub fn sizes(&mut self) -> HashMap<usize, usize> {
+        let mut sizes = HashMap::with_capacity(self.count);
+        for x in 0 .. self.len() {
+            let root = self.find(x);
+            sizes.insert(root, self.size[root]);
+        }
+        sizes
+    }
+
+    /// Returns the elements of each component, indexed by their
+    /// representative element.
+    pub fn components(&mut self) -> HashMap<usize, Vec<usize>> {
+        let mut components = HashMap::with_capacity(self.count);
+        for x in 0 .. self.len() {
+            let root = self.find(x);
+            components.entry(root).or_insert_with(Vec::new).push(x);
+        }
+        components
+    }
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    #[test]
+    fn unions() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.count(), 6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert_eq!(uf.count(), 3);
+
+        // Elements which are already in the same component.
+        assert!(!uf.union(0, 2));
+        assert!(!uf.union(3, 3));
+        assert_eq!(uf.count(), 3);
+
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+        assert_eq!(uf.size(2), 4);
+        assert_eq!(uf.size(5), 1);
+
+        let mut sizes: Vec<_> = uf.sizes().into_values().collect();
+        sizes.sort();
+        assert_eq!(sizes, [1, 1, 4]);
+
+        let mut components: Vec<_> = uf.components().into_values().collect();
+        components.sort();
+        assert_eq!(components, [vec![0, 1, 2, 3], vec![4], vec![5]]);
+    }
+
+    #[test]
+    fn push() {
+        let mut uf = UnionFind::new(1);
+        assert_eq!(uf.push(), 1);
+        assert_eq!(uf.len(), 2);
+        assert_eq!(uf.count(), 2);
+        assert!(uf.union(0, 1));
+        assert_eq!(uf.count(), 1);
+        assert_eq!(uf.size(1), 2);
+    }
+}
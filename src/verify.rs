@@ -0,0 +1,184 @@
+//
+// verify.rs
+// Copyright (C) 2017 Adrian Perez <aperez@igalia.com>
+// Distributed under terms of the MIT license.
+//
+
+use failure::{ Error, ResultExt };
+use std::fs::File;
+use std::io::Read;
+use std::path::{ Path, PathBuf };
+use std::time::{ Duration, Instant };
+use { name_input_error, solve, Part, DAYS };
+
+
+/// Directory used by default to look up puzzle inputs and answers.
+pub const DEFAULT_DIR: &str = "inputs";
+
+
+/// Returns the path to the puzzle input for a given day, which is
+/// expected to be named `dayNN.txt` inside the inputs directory.
+pub fn input_path(dir: &Path, day: u32) -> PathBuf
+{
+    dir.join(format!("day{:02}.txt", day))
+}
+
+
+pub fn read_file(path: &Path) -> Result<String, Error>
+{
+    let mut contents = String::new();
+    File::open(path)
+        .and_then(|mut f| f.read_to_string(&mut contents))
+        .with_context(|_| format!("Cannot read '{}'", path.display()))?;
+    Ok(contents)
+}
+
+
+#[derive(Debug, Clone)]
+pub struct Answer
+{
+    pub day: u32,
+    pub part: Part,
+    pub value: String,
+}
+
+
+/// Parses the contents of an answers file. Each line contains the day,
+/// the part, and the expected answer, separated by whitespace:
+///
+/// ```text
+/// # Comments and empty lines are ignored.
+/// 8 a 4877
+/// 8 b 5471
+/// ```
+pub fn parse_answers(contents: &str) -> Result<Vec<Answer>, Error>
+{
+    let mut answers = Vec::new();
+    for (lineno, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<_> = line.split_whitespace().collect();
+        ensure!(fields.len() == 3,
+                "Line {}: expected '<day> <part> <answer>'", lineno + 1);
+        let day = fields[0].parse::<u32>()
+            .with_context(|_| format!("Line {}: invalid day", lineno + 1))?;
+        ensure!((1 ..= DAYS).contains(&day),
+                "Line {}: day {} is not in the 1-{} range", lineno + 1, day, DAYS);
+        answers.push(Answer {
+            day,
+            part: fields[1].parse::<Part>()
+                .with_context(|_| format!("Line {}: invalid part", lineno + 1))?,
+            value: fields[2].to_string(),
+        });
+    }
+    Ok(answers)
+}
+
+
+#[derive(Debug)]
+pub enum Status
+{
+    Pass,
+    Mismatch(String),
+    Fail(Error),
+}
+
+
+#[derive(Debug)]
+pub struct Outcome
+{
+    pub answer: Answer,
+    pub status: Status,
+    pub elapsed: Duration,
+}
+
+impl Outcome
+{
+    #[inline]
+    pub fn passed(&self) -> bool {
+        matches!(self.status, Status::Pass)
+    }
+}
+
+
+/// Checks the solution for one day and part against its expected answer.
+/// The name of the input is used when reporting parsing errors.
+pub fn check(answer: &Answer, name: &str, input: &str) -> Outcome
+{
+    let start = Instant::now();
+    let result = solve(answer.day, answer.part, input)
+        .map_err(|e| name_input_error(e, name));
+    let elapsed = start.elapsed();
+
+    let status = match result {
+        Ok(ref value) if *value == answer.value => Status::Pass,
+        Ok(value) => Status::Mismatch(value),
+        Err(e) => Status::Fail(e),
+    };
+    Outcome { answer: answer.clone(), status, elapsed }
+}
+
+
+/// Reads the `answers` file from a directory, and checks each solution
+/// using the corresponding input from the same directory. Solutions for
+/// which the input cannot be read are reported as failures.
+pub fn verify_dir(dir: &Path) -> Result<Vec<Outcome>, Error>
+{
+    let answers = parse_answers(&read_file(&dir.join("answers"))?)?;
+    Ok(answers.iter().map(|answer| {
+        let path = input_path(dir, answer.day);
+        match read_file(&path) {
+            Ok(input) => check(answer, &path.display().to_string(), &input),
+            Err(e) => Outcome {
+                answer: answer.clone(),
+                status: Status::Fail(e),
+                elapsed: Duration::default(),
+            },
+        }
+    }).collect())
+}
+
+
+#[cfg(test)]
+mod tests
+{
+    use super::*;
+
+    fn answer(day: u32, part: Part, value: &str) -> Answer {
+        Answer { day, part, value: value.to_string() }
+    }
+
+    #[test]
+    fn parse_skips_comments() {
+        let answers = parse_answers("# Day one\n\n1 a 3\n  # Indented\n1 b 0\n").unwrap();
+        assert_eq!(answers.len(), 2);
+        assert_eq!((answers[0].day, answers[0].part, answers[0].value.as_str()), (1, Part::A, "3"));
+        assert_eq!((answers[1].day, answers[1].part, answers[1].value.as_str()), (1, Part::B, "0"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |contents| parse_answers(contents).unwrap_err().to_string();
+        assert_eq!(error("1 a 3\n\n1 b\n"), "Line 3: expected '<day> <part> <answer>'");
+        assert_eq!(error("1 a 3 4\n"), "Line 1: expected '<day> <part> <answer>'");
+        assert_eq!(error("# Comment\n0 a 3\n"), format!("Line 2: day 0 is not in the 1-{} range", DAYS));
+        assert_eq!(error("99 a 3\n"), format!("Line 1: day 99 is not in the 1-{} range", DAYS));
+        assert_eq!(error("x a 3\n"), "Line 1: invalid day");
+        assert_eq!(error("1 c 3\n"), "Line 1: invalid part");
+    }
+
+    #[test]
+    fn check_status() {
+        assert!(check(&answer(1, Part::A, "3"), "day01", "1122").passed());
+        match check(&answer(1, Part::A, "4"), "day01", "1122").status {
+            Status::Mismatch(ref value) => assert_eq!(value, "3"),
+            ref status => panic!("unexpected {:?}", status),
+        }
+        match check(&answer(8, Part::A, "1"), "day08", "a inc 1 if\n").status {
+            Status::Fail(ref e) => assert!(e.to_string().starts_with("day08:1: "), "{}", e),
+            ref status => panic!("unexpected {:?}", status),
+        }
+    }
+}